@@ -0,0 +1,125 @@
+use crate::operator::Operator;
+
+// Connectives that are associative and commutative, keyed by their symbol.
+fn ac_symbol(node: &Operator) -> Option<char> {
+    match node {
+        Operator::And(_, _) => Some('&'),
+        Operator::Or(_, _) => Some('|'),
+        Operator::Xor(_, _) => Some('^'),
+        Operator::Equals(_, _) => Some('='),
+        _ => None,
+    }
+}
+
+// Operands of a chain of nodes with the connective `symbol`, canonicalized.
+fn flatten(node: &Operator, symbol: char, acc: &mut Vec<Operator>) {
+    match node {
+        Operator::And(a, b) | Operator::Or(a, b) | Operator::Xor(a, b) | Operator::Equals(a, b)
+            if ac_symbol(node) == Some(symbol) =>
+        {
+            flatten(a, symbol, acc);
+            flatten(b, symbol, acc);
+        }
+        _ => acc.push(node.canonicalize()),
+    }
+}
+
+impl Operator {
+    // Flattens chains of And, Or, Xor and Equals, sorts their operands by
+    // their formula and nests them to the left again. Two trees are equal up
+    // to associativity and commutativity exactly when their canonical forms
+    // are equal, so the result works as a hash key.
+    pub fn canonicalize(&self) -> Operator {
+        if let Some(symbol) = ac_symbol(self) {
+            let mut items = vec![];
+            flatten(self, symbol, &mut items);
+            items.sort_by_cached_key(|item| item.to_string());
+            return items
+                .into_iter()
+                .reduce(|a, b| Operator::with_two(symbol, a, b))
+                .unwrap();
+        }
+        match self {
+            Operator::Not(a) => Operator::not(a.canonicalize()),
+            Operator::Implies(a, b) => Operator::with_two('>', a.canonicalize(), b.canonicalize()),
+            _ => self.clone(),
+        }
+    }
+}
+
+pub fn ac_equal(a: &Operator, b: &Operator) -> bool {
+    a.canonicalize() == b.canonicalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::table_diff::equivalent;
+
+    fn tree(formula: &str) -> Operator {
+        Operator::from_formula(formula).unwrap()
+    }
+
+    #[test]
+    fn commutativity_is_ignored() {
+        assert!(ac_equal(&tree("AB&"), &tree("BA&")));
+        assert!(ac_equal(&tree("AB^"), &tree("BA^")));
+        assert!(ac_equal(&tree("AB=!"), &tree("BA=!")));
+        assert!(!ac_equal(&tree("AB>"), &tree("BA>")));
+    }
+
+    #[test]
+    fn associativity_is_ignored() {
+        assert!(ac_equal(&tree("AB&C&"), &tree("ABC&&")));
+        assert!(ac_equal(&tree("AB|CD||"), &tree("DCBA|||")));
+        assert!(!ac_equal(&tree("AB&C|"), &tree("ABC|&")));
+    }
+
+    #[test]
+    fn chains_are_nested_to_the_left_in_order() {
+        assert_eq!(tree("CAB&&").canonicalize().to_string(), "AB&C&");
+        assert_eq!(tree("DC|BA&>").canonicalize().to_string(), "CD|AB&>");
+    }
+
+    #[test]
+    fn nested_chains_are_canonicalized() {
+        let a = tree("BA|C&D!&");
+        let b = tree("D!CAB|&&");
+
+        assert!(ac_equal(&a, &b));
+        assert_eq!(a.canonicalize().to_string(), "AB|C&D!&");
+    }
+
+    #[test]
+    fn normal_forms_compare_without_strings() {
+        let cnf = tree("AB&C|")
+            .to_negation_normal_form()
+            .to_conjunctive_normal_form();
+
+        assert!(ac_equal(&cnf, &tree("CB|CA|&")));
+        assert!(!ac_equal(&cnf, &tree("CB&CA&|")));
+    }
+
+    #[test]
+    fn canonical_forms_work_as_hash_keys() {
+        let keys: HashSet<Operator> = ["AB&C|", "CBA&|", "BA&C|", "CAB&|"]
+            .iter()
+            .map(|formula| tree(formula).canonicalize())
+            .collect();
+
+        assert_eq!(keys.len(), 1);
+    }
+
+    #[test]
+    fn canonicalization_is_idempotent_and_equivalent() {
+        for formula in ["AB>C^DA=|", "AB&C&!D|E^F^", "CB=A=", "AB|C>D&B!A!&|"] {
+            let operator = tree(formula);
+            let canonical = operator.canonicalize();
+
+            assert_eq!(canonical.canonicalize(), canonical, "{}", formula);
+            assert!(equivalent(&operator, &canonical), "{}", formula);
+        }
+    }
+}
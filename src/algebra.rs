@@ -0,0 +1,416 @@
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use crate::operator::Operator;
+
+// Elements know which algebra they belong to, so top and bottom are taken
+// relative to `self`. That keeps runtime-sized algebras (bitsets, sets over a
+// universe) on the same footing as `bool`.
+pub trait BooleanAlgebra: Clone {
+    fn meet(&self, other: &Self) -> Self;
+    fn join(&self, other: &Self) -> Self;
+    fn complement(&self) -> Self;
+    fn top(&self) -> Self;
+    fn bottom(&self) -> Self;
+
+    fn symmetric_difference(&self, other: &Self) -> Self {
+        self.meet(&other.complement())
+            .join(&self.complement().meet(other))
+    }
+
+    fn implication(&self, other: &Self) -> Self {
+        self.complement().join(other)
+    }
+
+    fn equivalence(&self, other: &Self) -> Self {
+        self.symmetric_difference(other).complement()
+    }
+}
+
+// Constants need an element to take top or bottom from. `evaluate` uses the
+// value of the first operand and returns None for a formula without operands,
+// as nothing tells which algebra its constants belong to. `evaluate_in` takes
+// the element explicitly so such formulas can be evaluated as well.
+pub fn evaluate<A: BooleanAlgebra, F: Fn(char) -> A>(operator: &Operator, values: &F) -> Option<A> {
+    let &c = operator.operands().first()?;
+    Some(evaluate_in(operator, &values(c), values))
+}
+
+pub fn evaluate_in<A: BooleanAlgebra, F: Fn(char) -> A>(
+    operator: &Operator,
+    element: &A,
+    values: &F,
+) -> A {
+    let eval = |a: &Operator| evaluate_in(a, element, values);
+    match operator {
+        Operator::And(a, b) => eval(a).meet(&eval(b)),
+        Operator::Or(a, b) => eval(a).join(&eval(b)),
+        Operator::Xor(a, b) => eval(a).symmetric_difference(&eval(b)),
+        Operator::Implies(a, b) => eval(a).implication(&eval(b)),
+        Operator::Equals(a, b) => eval(a).equivalence(&eval(b)),
+        Operator::Not(a) => eval(a).complement(),
+        Operator::Operand(c) => values(*c),
+        Operator::Constant(true) => element.top(),
+        Operator::Constant(false) => element.bottom(),
+    }
+}
+
+impl BooleanAlgebra for bool {
+    fn meet(&self, other: &Self) -> Self {
+        *self && *other
+    }
+
+    fn join(&self, other: &Self) -> Self {
+        *self || *other
+    }
+
+    fn complement(&self) -> Self {
+        !*self
+    }
+
+    fn top(&self) -> Self {
+        true
+    }
+
+    fn bottom(&self) -> Self {
+        false
+    }
+
+    fn symmetric_difference(&self, other: &Self) -> Self {
+        *self ^ *other
+    }
+}
+
+macro_rules! word_algebra {
+    ($($word:ty),*) => {
+        $(
+            impl BooleanAlgebra for $word {
+                fn meet(&self, other: &Self) -> Self {
+                    *self & *other
+                }
+
+                fn join(&self, other: &Self) -> Self {
+                    *self | *other
+                }
+
+                fn complement(&self) -> Self {
+                    !*self
+                }
+
+                fn top(&self) -> Self {
+                    <$word>::MAX
+                }
+
+                fn bottom(&self) -> Self {
+                    0
+                }
+
+                fn symmetric_difference(&self, other: &Self) -> Self {
+                    *self ^ *other
+                }
+            }
+        )*
+    };
+}
+
+word_algebra!(u8, u16, u32, u64, u128, usize);
+
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+pub struct BitSet {
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new(len: usize) -> BitSet {
+        BitSet {
+            len,
+            words: vec![0; len.div_ceil(64)],
+        }
+    }
+
+    pub fn from_indices(len: usize, indices: &[usize]) -> BitSet {
+        let mut set = BitSet::new(len);
+        for &i in indices {
+            set.insert(i);
+        }
+        set
+    }
+
+    pub fn from_words(len: usize, words: Vec<u64>) -> BitSet {
+        if words.len() != len.div_ceil(64) {
+            panic!("Bitset of length {} needs {} words", len, len.div_ceil(64));
+        }
+        BitSet { len, words }.clear_unused_bits()
+    }
+
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn insert(&mut self, i: usize) {
+        if i >= self.len {
+            panic!("Index {} out of range for bitset of length {}", i, self.len);
+        }
+        self.words[i / 64] |= 1 << (i % 64);
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        i < self.len && self.words[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn indices(&self) -> Vec<usize> {
+        (0..self.len).filter(|&i| self.contains(i)).collect()
+    }
+
+    fn zip_with(&self, other: &BitSet, f: impl Fn(u64, u64) -> u64) -> BitSet {
+        if self.len != other.len {
+            panic!("Bitsets must have the same length");
+        }
+        BitSet {
+            len: self.len,
+            words: self
+                .words
+                .iter()
+                .zip(other.words.iter())
+                .map(|(a, b)| f(*a, *b))
+                .collect(),
+        }
+    }
+
+    fn clear_unused_bits(mut self) -> BitSet {
+        if !self.len.is_multiple_of(64) {
+            let last = self.words.len() - 1;
+            self.words[last] &= (1 << (self.len % 64)) - 1;
+        }
+        self
+    }
+}
+
+impl BooleanAlgebra for BitSet {
+    fn meet(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    fn join(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    fn complement(&self) -> Self {
+        BitSet {
+            len: self.len,
+            words: self.words.iter().map(|w| !w).collect(),
+        }
+        .clear_unused_bits()
+    }
+
+    fn top(&self) -> Self {
+        BitSet {
+            len: self.len,
+            words: vec![u64::MAX; self.words.len()],
+        }
+        .clear_unused_bits()
+    }
+
+    fn bottom(&self) -> Self {
+        BitSet::new(self.len)
+    }
+
+    fn symmetric_difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a ^ b)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct FiniteSet {
+    universe: Rc<HashSet<i32>>,
+    elements: HashSet<i32>,
+}
+
+impl FiniteSet {
+    pub fn new(universe: Rc<HashSet<i32>>, elements: HashSet<i32>) -> FiniteSet {
+        if !elements.is_subset(&universe) {
+            panic!("Elements must be part of the universe");
+        }
+        FiniteSet { universe, elements }
+    }
+
+    pub fn elements(&self) -> &HashSet<i32> {
+        &self.elements
+    }
+
+    fn with_elements(&self, elements: HashSet<i32>) -> FiniteSet {
+        FiniteSet {
+            universe: self.universe.clone(),
+            elements,
+        }
+    }
+}
+
+impl BooleanAlgebra for FiniteSet {
+    fn meet(&self, other: &Self) -> Self {
+        self.with_elements(
+            self.elements
+                .intersection(&other.elements)
+                .cloned()
+                .collect(),
+        )
+    }
+
+    fn join(&self, other: &Self) -> Self {
+        self.with_elements(self.elements.union(&other.elements).cloned().collect())
+    }
+
+    fn complement(&self) -> Self {
+        self.with_elements(self.universe.difference(&self.elements).cloned().collect())
+    }
+
+    fn top(&self) -> Self {
+        self.with_elements((*self.universe).clone())
+    }
+
+    fn bottom(&self) -> Self {
+        self.with_elements(HashSet::new())
+    }
+
+    fn symmetric_difference(&self, other: &Self) -> Self {
+        self.with_elements(
+            self.elements
+                .symmetric_difference(&other.elements)
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn sorted(set: &FiniteSet) -> Vec<i32> {
+        let mut elements: Vec<i32> = set.elements().iter().cloned().collect();
+        elements.sort();
+        elements
+    }
+
+    #[test]
+    fn evaluating_with_bools_works() {
+        let tree = Operator::from_formula("AB>C^").unwrap();
+        let values = HashMap::from([('A', true), ('B', false), ('C', true)]);
+
+        assert!(evaluate(&tree, &|c| values[&c]).unwrap());
+    }
+
+    #[test]
+    fn evaluating_with_words_evaluates_every_bit() {
+        let tree = Operator::from_formula("AB=").unwrap();
+        let result: u8 = evaluate(&tree, &|c| if c == 'A' { 0b1100 } else { 0b1010 }).unwrap();
+
+        assert_eq!(result, 0b1111_1001);
+    }
+
+    #[test]
+    fn constant_formulas_need_an_element() {
+        let tree = Operator::from_formula("10>!").unwrap();
+
+        assert_eq!(evaluate(&tree, &|_| true), None);
+        assert_eq!(evaluate_in(&tree, &0u8, &|_| 0), u8::MAX);
+        assert!(evaluate_in(&tree, &false, &|_| false));
+        assert_eq!(
+            evaluate(&Operator::from_formula("A1&").unwrap(), &|_| 0b0110u8),
+            Some(0b0110)
+        );
+    }
+
+    #[test]
+    fn bitset_complement_stays_within_length() {
+        let set = BitSet::from_indices(70, &[0, 65]);
+        let complement = set.complement();
+
+        assert_eq!(complement.count_ones(), 68);
+        assert!(!complement.contains(65));
+        assert_eq!(set.join(&complement), set.top());
+        assert_eq!(set.meet(&complement), set.bottom());
+    }
+
+    #[test]
+    fn evaluating_with_bitsets_works() {
+        let tree = Operator::from_formula("AB!&").unwrap();
+        let a = BitSet::from_indices(4, &[0, 1, 2]);
+        let b = BitSet::from_indices(4, &[1, 3]);
+        let result = evaluate(&tree, &|c| if c == 'A' { a.clone() } else { b.clone() }).unwrap();
+
+        assert_eq!(result.indices(), vec![0, 2]);
+    }
+
+    #[test]
+    fn evaluating_with_finite_sets_works() {
+        let universe = Rc::new(HashSet::from([1, 2, 3, 4]));
+        let a = FiniteSet::new(universe.clone(), HashSet::from([1, 2]));
+        let b = FiniteSet::new(universe.clone(), HashSet::from([2, 3]));
+        let values = |c| if c == 'A' { a.clone() } else { b.clone() };
+
+        let xor = evaluate(&Operator::from_formula("AB^").unwrap(), &values).unwrap();
+        let implies = evaluate(&Operator::from_formula("AB>").unwrap(), &values).unwrap();
+
+        assert_eq!(sorted(&xor), vec![1, 3]);
+        assert_eq!(sorted(&implies), vec![2, 3, 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn finite_set_outside_universe_panics() {
+        FiniteSet::new(Rc::new(HashSet::from([1])), HashSet::from([2]));
+    }
+
+    #[derive(Clone, PartialEq, Debug)]
+    struct Parity(bool);
+
+    impl BooleanAlgebra for Parity {
+        fn meet(&self, other: &Self) -> Self {
+            Parity(self.0 && other.0)
+        }
+
+        fn join(&self, other: &Self) -> Self {
+            Parity(self.0 || other.0)
+        }
+
+        fn complement(&self) -> Self {
+            Parity(!self.0)
+        }
+
+        fn top(&self) -> Self {
+            Parity(true)
+        }
+
+        fn bottom(&self) -> Self {
+            Parity(false)
+        }
+    }
+
+    #[test]
+    fn default_derived_operations_work_for_custom_algebras() {
+        let tree = Operator::from_formula("AB^AB=&").unwrap();
+
+        for a in [false, true] {
+            for b in [false, true] {
+                let result = evaluate(&tree, &|c| Parity(if c == 'A' { a } else { b })).unwrap();
+                assert_eq!(result, Parity(false));
+            }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::fmt;
+
+use crate::fingerprint::table_bits;
+use crate::operator::Operator;
+use crate::truth_table::TruthTable;
+
+// Bits with bit `k` clear, for the in-word steps of the Möbius transform.
+const LOW_HALVES: [u64; 6] = [
+    0x5555_5555_5555_5555,
+    0x3333_3333_3333_3333,
+    0x0F0F_0F0F_0F0F_0F0F,
+    0x00FF_00FF_00FF_00FF,
+    0x0000_FFFF_0000_FFFF,
+    0x0000_0000_FFFF_FFFF,
+];
+
+// Turns a truth table (bit `i` is row `i`) into the coefficients of its
+// Zhegalkin polynomial (bit `m` is the monomial with operand mask `m`).
+fn mobius_transform(words: &mut [u64], n: usize) {
+    for (k, low) in LOW_HALVES.iter().enumerate().take(n) {
+        for word in words.iter_mut() {
+            *word ^= (*word & low) << (1 << k);
+        }
+    }
+    for k in 6..n {
+        let stride = 1 << (k - 6);
+        for i in 0..words.len() {
+            if i & stride != 0 {
+                words[i] ^= words[i ^ stride];
+            }
+        }
+    }
+}
+
+// An Xor of conjunctions of operands. Monomials use the same bit layout as
+// truth table rows, 0 is the constant 1.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct AlgebraicNormalForm {
+    operands: Vec<char>,
+    monomials: Vec<u64>,
+}
+
+impl AlgebraicNormalForm {
+    pub fn new(operands: &[char], mut monomials: Vec<u64>) -> AlgebraicNormalForm {
+        let len = 1u64 << operands.len();
+        if let Some(m) = monomials.iter().find(|&&m| m >= len) {
+            panic!(
+                "Monomial {} out of range for {} operands",
+                m,
+                operands.len()
+            );
+        }
+        monomials.sort_by_key(|&m| (m.count_ones(), std::cmp::Reverse(m)));
+        // Equal monomials cancel out in pairs.
+        let mut kept: Vec<u64> = vec![];
+        for m in monomials {
+            if kept.last() == Some(&m) {
+                kept.pop();
+            } else {
+                kept.push(m);
+            }
+        }
+        AlgebraicNormalForm {
+            operands: operands.to_vec(),
+            monomials: kept,
+        }
+    }
+
+    pub fn from_table(table: &TruthTable) -> AlgebraicNormalForm {
+        let mut words = table_bits(table).words().to_vec();
+        mobius_transform(&mut words, table.operands().len());
+        let monomials = words
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &word)| {
+                (0..64)
+                    .filter(move |j| word >> j & 1 == 1)
+                    .map(move |j| i as u64 * 64 + j)
+            })
+            .collect();
+        AlgebraicNormalForm::new(table.operands(), monomials)
+    }
+
+    pub fn operands(&self) -> &[char] {
+        &self.operands
+    }
+
+    pub fn monomials(&self) -> &[u64] {
+        &self.monomials
+    }
+
+    pub fn terms(&self) -> Vec<Vec<char>> {
+        let n = self.operands.len();
+        self.monomials
+            .iter()
+            .map(|&m| {
+                (0..n)
+                    .filter(|i| m >> (n - 1 - i) & 1 == 1)
+                    .map(|i| self.operands[i])
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn degree(&self) -> u32 {
+        self.monomials
+            .iter()
+            .map(|m| m.count_ones())
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn to_operator(&self) -> Operator {
+        self.terms()
+            .into_iter()
+            .map(|term| Operator::conjunction(term.into_iter().map(Operator::operand)))
+            .reduce(|a, b| Operator::with_two('^', a, b))
+            .unwrap_or(Operator::Constant(false))
+    }
+}
+
+impl fmt::Display for AlgebraicNormalForm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.monomials.is_empty() {
+            return write!(f, "0");
+        }
+        let terms: Vec<String> = self
+            .terms()
+            .into_iter()
+            .map(|term| {
+                if term.is_empty() {
+                    "1".to_string()
+                } else {
+                    term.into_iter().collect()
+                }
+            })
+            .collect();
+        write!(f, "{}", terms.join(" ^ "))
+    }
+}
+
+impl Operator {
+    pub fn to_algebraic_normal_form(&self) -> AlgebraicNormalForm {
+        AlgebraicNormalForm::from_table(&TruthTable::new(self.clone(), &self.operands()))
+    }
+}
+
+pub fn algebraic_normal_form(formula: &str) -> String {
+    match Operator::from_formula(formula) {
+        Some(operator) => operator.to_algebraic_normal_form().to_string(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table_diff::equivalent;
+
+    fn tree(formula: &str) -> Operator {
+        Operator::from_formula(formula).unwrap()
+    }
+
+    #[test]
+    fn basic_connectives_work() {
+        assert_eq!(algebraic_normal_form("AB^"), "A ^ B");
+        assert_eq!(algebraic_normal_form("AB&"), "AB");
+        assert_eq!(algebraic_normal_form("AB|"), "A ^ B ^ AB");
+        assert_eq!(algebraic_normal_form("A!"), "1 ^ A");
+        assert_eq!(algebraic_normal_form("AB>"), "1 ^ A ^ AB");
+    }
+
+    #[test]
+    fn constant_functions_work() {
+        assert_eq!(algebraic_normal_form("AA^"), "0");
+        assert_eq!(algebraic_normal_form("AA="), "1");
+        assert_eq!(algebraic_normal_form(""), "");
+    }
+
+    #[test]
+    fn monomials_are_sorted_by_degree() {
+        let anf = tree("AB&BC&|AC&|").to_algebraic_normal_form();
+
+        assert_eq!(
+            anf.terms(),
+            vec![vec!['A', 'B'], vec!['A', 'C'], vec!['B', 'C']]
+        );
+        assert_eq!(anf.monomials(), &[0b110, 0b101, 0b011]);
+    }
+
+    #[test]
+    fn degree_works() {
+        assert_eq!(tree("AB^C^").to_algebraic_normal_form().degree(), 1);
+        assert_eq!(tree("AB&BC&|AC&|").to_algebraic_normal_form().degree(), 2);
+        assert_eq!(tree("ABCD&&&").to_algebraic_normal_form().degree(), 4);
+        assert_eq!(tree("AA^").to_algebraic_normal_form().degree(), 0);
+    }
+
+    #[test]
+    fn conversion_back_is_equivalent() {
+        for formula in ["AB|C>", "AB=C!&D|", "AB>C^DE&|F=", "ABCDEFGH|&^>=|&!"] {
+            let operator = tree(formula);
+            let anf = operator.to_algebraic_normal_form();
+
+            assert!(equivalent(&operator, &anf.to_operator()), "{}", formula);
+        }
+    }
+
+    #[test]
+    fn transform_works_across_words() {
+        let anf = tree("ABCDEFGH&&&&&&&").to_algebraic_normal_form();
+
+        assert_eq!(anf.monomials(), &[0b1111_1111]);
+    }
+
+    #[test]
+    fn equal_monomials_cancel() {
+        let anf = AlgebraicNormalForm::new(&['A', 'B'], vec![3, 1, 0, 2, 1]);
+
+        assert_eq!(anf.monomials(), &[0, 2, 3]);
+        assert_eq!(anf.to_string(), "1 ^ A ^ AB");
+    }
+}
@@ -0,0 +1,735 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::operator::Operator;
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Connective {
+    And,
+    Or,
+    Xor,
+    Implies,
+    Equals,
+    Not,
+    Nand,
+    Nor,
+    True,
+    False,
+}
+
+impl Connective {
+    pub fn arity(self) -> usize {
+        match self {
+            Connective::True | Connective::False => 0,
+            Connective::Not => 1,
+            _ => 2,
+        }
+    }
+
+    pub fn apply(self, args: &[bool]) -> bool {
+        match self {
+            Connective::And => args[0] && args[1],
+            Connective::Or => args[0] || args[1],
+            Connective::Xor => args[0] ^ args[1],
+            Connective::Implies => !args[0] || args[1],
+            Connective::Equals => args[0] == args[1],
+            Connective::Not => !args[0],
+            Connective::Nand => !(args[0] && args[1]),
+            Connective::Nor => !(args[0] || args[1]),
+            Connective::True => true,
+            Connective::False => false,
+        }
+    }
+
+    // The arguments of every row, row `i` has bit `arity - 1 - j` as argument
+    // `j` like truth table rows.
+    fn rows(self) -> Vec<Vec<bool>> {
+        let n = self.arity();
+        (0..1usize << n)
+            .map(|i| (0..n).map(|j| i >> (n - 1 - j) & 1 == 1).collect())
+            .collect()
+    }
+
+    pub fn is_in(self, class: PostClass) -> bool {
+        let n = self.arity();
+        let rows = self.rows();
+        match class {
+            PostClass::PreservesFalse => !self.apply(&vec![false; n]),
+            PostClass::PreservesTrue => self.apply(&vec![true; n]),
+            PostClass::Monotone => rows.iter().all(|x| {
+                rows.iter().all(|y| {
+                    !x.iter().zip(y).all(|(a, b)| a <= b) || self.apply(x) <= self.apply(y)
+                })
+            }),
+            PostClass::SelfDual => rows.iter().all(|x| {
+                let flipped: Vec<bool> = x.iter().map(|b| !b).collect();
+                self.apply(&flipped) != self.apply(x)
+            }),
+            PostClass::Affine => {
+                let zero = self.apply(&vec![false; n]);
+                rows.iter().all(|x| {
+                    rows.iter().all(|y| {
+                        let sum: Vec<bool> = x.iter().zip(y).map(|(a, b)| a ^ b).collect();
+                        !(self.apply(&sum) ^ self.apply(x) ^ self.apply(y) ^ zero)
+                    })
+                })
+            }
+        }
+    }
+}
+
+// Post's five maximal clones. A set of connectives is functionally complete
+// exactly when no class contains all of them.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum PostClass {
+    PreservesFalse,
+    PreservesTrue,
+    Monotone,
+    SelfDual,
+    Affine,
+}
+
+const POST_CLASSES: [PostClass; 5] = [
+    PostClass::PreservesFalse,
+    PostClass::PreservesTrue,
+    PostClass::Monotone,
+    PostClass::SelfDual,
+    PostClass::Affine,
+];
+
+// The classes that contain every connective of the set.
+pub fn post_classes(connectives: &[Connective]) -> Vec<PostClass> {
+    POST_CLASSES
+        .into_iter()
+        .filter(|&class| connectives.iter().all(|c| c.is_in(class)))
+        .collect()
+}
+
+pub fn is_functionally_complete(connectives: &[Connective]) -> bool {
+    post_classes(connectives).is_empty()
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Basis {
+    Nand,
+    Nor,
+    AndNot,
+    OrNot,
+    ImpliesFalse,
+}
+
+impl Basis {
+    pub fn connectives(self) -> &'static [Connective] {
+        match self {
+            Basis::Nand => &[Connective::Nand],
+            Basis::Nor => &[Connective::Nor],
+            Basis::AndNot => &[Connective::And, Connective::Not],
+            Basis::OrNot => &[Connective::Or, Connective::Not],
+            Basis::ImpliesFalse => &[Connective::Implies, Connective::False],
+        }
+    }
+
+    // Whether the tree only uses the connectives of the basis. A Nand is a
+    // Not right above an And, a Nor a Not right above an Or.
+    pub fn expresses(self, operator: &Operator) -> bool {
+        match (self, operator) {
+            (_, Operator::Operand(_)) => true,
+            (Basis::Nand, Operator::Not(a)) => match a.as_ref() {
+                Operator::And(b, c) => self.expresses(b) && self.expresses(c),
+                _ => false,
+            },
+            (Basis::Nor, Operator::Not(a)) => match a.as_ref() {
+                Operator::Or(b, c) => self.expresses(b) && self.expresses(c),
+                _ => false,
+            },
+            (Basis::AndNot, Operator::And(a, b))
+            | (Basis::OrNot, Operator::Or(a, b))
+            | (Basis::ImpliesFalse, Operator::Implies(a, b)) => {
+                self.expresses(a) && self.expresses(b)
+            }
+            (Basis::AndNot, Operator::Not(a)) | (Basis::OrNot, Operator::Not(a)) => {
+                self.expresses(a)
+            }
+            (Basis::ImpliesFalse, Operator::Constant(false)) => true,
+            _ => false,
+        }
+    }
+}
+
+// A wire of a circuit: an operand of the formula, the constant false or the
+// output of a gate.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+pub enum Signal {
+    Input(char),
+    False,
+    Gate(usize),
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Signal::Input(c) => write!(f, "{}", c),
+            Signal::False => write!(f, "0"),
+            Signal::Gate(i) => write!(f, "g{}", i + 1),
+        }
+    }
+}
+
+// The inputs of a gate are signals of earlier gates.
+#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+pub struct Gate {
+    pub connective: Connective,
+    pub inputs: Vec<Signal>,
+}
+
+impl fmt::Display for Gate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self.connective {
+            Connective::And => "and",
+            Connective::Or => "or",
+            Connective::Not => "not",
+            Connective::Nand => "nand",
+            Connective::Nor => "nor",
+            Connective::Implies => "implies",
+            c => panic!("{:?} is not a gate of any basis", c),
+        };
+        let inputs: Vec<String> = self.inputs.iter().map(Signal::to_string).collect();
+        write!(f, "{}({})", name, inputs.join(", "))
+    }
+}
+
+// A formula as gates of a basis. Every gate is built once and the gates using
+// it refer to its signal, so the circuit stays small where the tree it stands
+// for doubles at every level.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Circuit {
+    basis: Basis,
+    gates: Vec<Gate>,
+    output: Signal,
+}
+
+impl Circuit {
+    pub fn basis(&self) -> Basis {
+        self.basis
+    }
+
+    pub fn gates(&self) -> &[Gate] {
+        &self.gates
+    }
+
+    pub fn output(&self) -> Signal {
+        self.output
+    }
+
+    // The tree the circuit stands for, with Nand as a Not above an And and
+    // Nor as a Not above an Or. It repeats every shared gate, so it can be
+    // exponentially larger than the circuit.
+    pub fn to_operator(&self) -> Operator {
+        let mut trees: Vec<Operator> = Vec::with_capacity(self.gates.len());
+        for gate in &self.gates {
+            let input = |i: usize| match gate.inputs[i] {
+                Signal::Input(c) => Operator::Operand(c),
+                Signal::False => Operator::Constant(false),
+                Signal::Gate(j) => trees[j].clone(),
+            };
+            let tree = match gate.connective {
+                Connective::And => Operator::and(input(0), input(1)),
+                Connective::Or => Operator::or(input(0), input(1)),
+                Connective::Not => Operator::not(input(0)),
+                Connective::Nand => Operator::not(Operator::and(input(0), input(1))),
+                Connective::Nor => Operator::not(Operator::or(input(0), input(1))),
+                Connective::Implies => Operator::with_two('>', input(0), input(1)),
+                c => panic!("{:?} is not a gate of any basis", c),
+            };
+            trees.push(tree);
+        }
+        match self.output {
+            Signal::Input(c) => Operator::Operand(c),
+            Signal::False => Operator::Constant(false),
+            Signal::Gate(i) => trees.swap_remove(i),
+        }
+    }
+}
+
+// A netlist, `g1 = nand(A, B); g2 = nand(A, g1); ...`, ending with the
+// output.
+impl fmt::Display for Circuit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, gate) in self.gates.iter().enumerate() {
+            if self.output == Signal::Gate(i) && i + 1 == self.gates.len() {
+                return write!(f, "{}", gate);
+            }
+            write!(f, "{} = {}; ", Signal::Gate(i), gate)?;
+        }
+        write!(f, "{}", self.output)
+    }
+}
+
+// Builds a circuit, a gate asked for twice is looked up instead of added.
+struct Builder {
+    basis: Basis,
+    gates: Vec<Gate>,
+    ids: HashMap<Gate, usize>,
+}
+
+impl Builder {
+    fn gate(&mut self, connective: Connective, inputs: Vec<Signal>) -> Signal {
+        let gate = Gate { connective, inputs };
+        if let Some(&i) = self.ids.get(&gate) {
+            return Signal::Gate(i);
+        }
+        self.gates.push(gate.clone());
+        self.ids.insert(gate, self.gates.len() - 1);
+        Signal::Gate(self.gates.len() - 1)
+    }
+
+    fn nand(&mut self, a: Signal, b: Signal) -> Signal {
+        self.gate(Connective::Nand, vec![a, b])
+    }
+
+    fn nor(&mut self, a: Signal, b: Signal) -> Signal {
+        self.gate(Connective::Nor, vec![a, b])
+    }
+
+    fn implies(&mut self, a: Signal, b: Signal) -> Signal {
+        self.gate(Connective::Implies, vec![a, b])
+    }
+
+    // The input of `a` when `a` is the negation of the basis.
+    fn negated(&self, a: Signal) -> Option<Signal> {
+        let Signal::Gate(i) = a else {
+            return None;
+        };
+        let gate = &self.gates[i];
+        match (gate.connective, &gate.inputs[..]) {
+            (Connective::Nand | Connective::Nor, &[b, c]) if b == c => Some(b),
+            (Connective::Not, &[b]) => Some(b),
+            (Connective::Implies, &[b, Signal::False]) => Some(b),
+            _ => None,
+        }
+    }
+
+    fn not(&mut self, a: Signal) -> Signal {
+        if let Some(inner) = self.negated(a) {
+            return inner;
+        }
+        match self.basis {
+            Basis::Nand => self.nand(a, a),
+            Basis::Nor => self.nor(a, a),
+            Basis::AndNot | Basis::OrNot => self.gate(Connective::Not, vec![a]),
+            Basis::ImpliesFalse => self.implies(a, Signal::False),
+        }
+    }
+
+    fn and(&mut self, a: Signal, b: Signal) -> Signal {
+        match self.basis {
+            Basis::Nand => {
+                let t = self.nand(a, b);
+                self.not(t)
+            }
+            Basis::AndNot => self.gate(Connective::And, vec![a, b]),
+            Basis::Nor | Basis::OrNot | Basis::ImpliesFalse => {
+                let (a, b) = (self.not(a), self.not(b));
+                let t = self.or(a, b);
+                self.not(t)
+            }
+        }
+    }
+
+    fn or(&mut self, a: Signal, b: Signal) -> Signal {
+        match self.basis {
+            Basis::Nor => {
+                let t = self.nor(a, b);
+                self.not(t)
+            }
+            Basis::OrNot => self.gate(Connective::Or, vec![a, b]),
+            Basis::ImpliesFalse => {
+                let a = self.not(a);
+                self.implies(a, b)
+            }
+            Basis::Nand | Basis::AndNot => {
+                let (a, b) = (self.not(a), self.not(b));
+                let t = self.and(a, b);
+                self.not(t)
+            }
+        }
+    }
+
+    fn xor(&mut self, a: Signal, b: Signal) -> Signal {
+        match self.basis {
+            // Four Nand gates, the first one shared.
+            Basis::Nand => {
+                let t = self.nand(a, b);
+                let (left, right) = (self.nand(a, t), self.nand(b, t));
+                self.nand(left, right)
+            }
+            // Four Nor gates give the equivalence.
+            Basis::Nor => {
+                let t = self.nor(a, b);
+                let (left, right) = (self.nor(a, t), self.nor(b, t));
+                let equals = self.nor(left, right);
+                self.not(equals)
+            }
+            _ => {
+                let (not_a, not_b) = (self.not(a), self.not(b));
+                let left = self.and(a, not_b);
+                let right = self.and(not_a, b);
+                self.or(left, right)
+            }
+        }
+    }
+
+    // `anchor` is an operand of the formula, constants are built from it.
+    fn constant(&mut self, value: bool, anchor: Option<char>) -> Signal {
+        if self.basis == Basis::ImpliesFalse {
+            return if value {
+                self.not(Signal::False)
+            } else {
+                Signal::False
+            };
+        }
+        let x = Signal::Input(anchor.expect("No operand to build a constant from"));
+        let not_x = self.not(x);
+        let tautology = match self.basis {
+            Basis::Nand => self.nand(x, not_x),
+            _ => self.or(x, not_x),
+        };
+        if value {
+            tautology
+        } else {
+            self.not(tautology)
+        }
+    }
+
+    fn convert(&mut self, operator: &Operator, anchor: Option<char>) -> Signal {
+        match operator {
+            Operator::Operand(c) => Signal::Input(*c),
+            Operator::Constant(b) => self.constant(*b, anchor),
+            Operator::Not(a) => {
+                let a = self.convert(a, anchor);
+                self.not(a)
+            }
+            Operator::And(a, b)
+            | Operator::Or(a, b)
+            | Operator::Xor(a, b)
+            | Operator::Implies(a, b)
+            | Operator::Equals(a, b) => {
+                let (a, b) = (self.convert(a, anchor), self.convert(b, anchor));
+                match operator {
+                    Operator::And(_, _) => self.and(a, b),
+                    Operator::Or(_, _) => self.or(a, b),
+                    Operator::Xor(_, _) => self.xor(a, b),
+                    Operator::Equals(_, _) => {
+                        let t = self.xor(a, b);
+                        self.not(t)
+                    }
+                    _ => match self.basis {
+                        Basis::ImpliesFalse => self.implies(a, b),
+                        _ => {
+                            let not_a = self.not(a);
+                            self.or(not_a, b)
+                        }
+                    },
+                }
+            }
+        }
+    }
+
+    // The circuit of the gates `output` depends on, gates built on the way
+    // and dropped again, like the one under a double negation, are left out.
+    fn finish(self, output: Signal) -> Circuit {
+        let mut used = vec![false; self.gates.len()];
+        if let Signal::Gate(i) = output {
+            used[i] = true;
+        }
+        for i in (0..self.gates.len()).rev() {
+            if used[i] {
+                for input in &self.gates[i].inputs {
+                    if let Signal::Gate(j) = *input {
+                        used[j] = true;
+                    }
+                }
+            }
+        }
+        let mut ids = vec![0; self.gates.len()];
+        let renumber = |signal: Signal, ids: &[usize]| match signal {
+            Signal::Gate(i) => Signal::Gate(ids[i]),
+            signal => signal,
+        };
+        let mut gates = vec![];
+        for (i, gate) in self.gates.into_iter().enumerate() {
+            if used[i] {
+                ids[i] = gates.len();
+                gates.push(Gate {
+                    connective: gate.connective,
+                    inputs: gate.inputs.iter().map(|&s| renumber(s, &ids)).collect(),
+                });
+            }
+        }
+        Circuit {
+            basis: self.basis,
+            gates,
+            output: renumber(output, &ids),
+        }
+    }
+}
+
+// An equivalent circuit that only uses the connectives of `basis`. Constants
+// are built from an operand of the formula, so a formula without operands has
+// no such circuit unless the basis has a constant, and None is returned.
+pub fn to_basis(operator: &Operator, basis: Basis) -> Option<Circuit> {
+    let anchor = operator.operands().first().copied();
+    if anchor.is_none() && basis != Basis::ImpliesFalse {
+        return None;
+    }
+    let mut builder = Builder {
+        basis,
+        gates: vec![],
+        ids: HashMap::new(),
+    };
+    let output = builder.convert(operator, anchor);
+    Some(builder.finish(output))
+}
+
+// Nodes of the tree the circuit stands for, distinct subtrees of it (the
+// circuit reuses them) and gates of the basis.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct SizeReport {
+    pub nodes: usize,
+    pub shared_nodes: usize,
+    pub gates: usize,
+}
+
+impl fmt::Display for SizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} nodes, {} after sharing, {} gates",
+            self.nodes, self.shared_nodes, self.gates
+        )
+    }
+}
+
+// Gates were deduplicated while building, so the counts come from the gate
+// list without expanding the tree. A Nand or Nor is two nodes of the tree.
+pub fn size_report(circuit: &Circuit) -> SizeReport {
+    let own = |gate: &Gate| match gate.connective {
+        Connective::Nand | Connective::Nor => 2,
+        _ => 1,
+    };
+    let mut sizes: Vec<usize> = Vec::with_capacity(circuit.gates.len());
+    let mut leaves: HashSet<Signal> = HashSet::new();
+    let size = |signal: Signal, sizes: &[usize]| match signal {
+        Signal::Gate(i) => sizes[i],
+        _ => 1,
+    };
+    for gate in &circuit.gates {
+        let inputs = gate.inputs.iter().map(|&s| size(s, &sizes));
+        sizes.push(inputs.fold(own(gate), usize::saturating_add));
+        leaves.extend(gate.inputs.iter().filter(|s| !matches!(s, Signal::Gate(_))));
+    }
+    if circuit.gates.is_empty() {
+        leaves.insert(circuit.output);
+    }
+    SizeReport {
+        nodes: size(circuit.output, &sizes),
+        shared_nodes: circuit.gates.iter().map(own).sum::<usize>() + leaves.len(),
+        gates: circuit.gates.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table_diff::equivalent;
+
+    const BASES: [Basis; 5] = [
+        Basis::Nand,
+        Basis::Nor,
+        Basis::AndNot,
+        Basis::OrNot,
+        Basis::ImpliesFalse,
+    ];
+
+    fn tree(formula: &str) -> Operator {
+        Operator::from_formula(formula).unwrap()
+    }
+
+    #[test]
+    fn conversions_are_equivalent_and_use_the_basis() {
+        for formula in ["AB&C|", "AB^C=", "AB>!", "A1&B0|^", "AB=C>D!&", "A!!"] {
+            let operator = tree(formula);
+            for basis in BASES {
+                let circuit = to_basis(&operator, basis).unwrap();
+                let converted = circuit.to_operator();
+
+                assert!(circuit
+                    .gates()
+                    .iter()
+                    .all(|gate| basis.connectives().contains(&gate.connective)));
+                assert!(basis.expresses(&converted), "{} {:?}", formula, basis);
+                assert!(equivalent(&operator, &converted), "{} {:?}", formula, basis);
+            }
+        }
+    }
+
+    #[test]
+    fn small_conversions_are_the_textbook_ones() {
+        assert_eq!(
+            to_basis(&tree("A!"), Basis::Nand)
+                .unwrap()
+                .to_operator()
+                .to_string(),
+            "AA&!"
+        );
+        assert_eq!(
+            to_basis(&tree("AB&"), Basis::Nand)
+                .unwrap()
+                .to_operator()
+                .to_string(),
+            "AB&!AB&!&!"
+        );
+        assert_eq!(
+            to_basis(&tree("AB|"), Basis::Nor)
+                .unwrap()
+                .to_operator()
+                .to_string(),
+            "AB|!AB|!|!"
+        );
+        assert_eq!(
+            to_basis(&tree("AB|"), Basis::AndNot)
+                .unwrap()
+                .to_operator()
+                .to_string(),
+            "A!B!&!"
+        );
+        assert_eq!(
+            to_basis(&tree("A!"), Basis::ImpliesFalse)
+                .unwrap()
+                .to_operator()
+                .to_string(),
+            "A0>"
+        );
+        assert_eq!(
+            to_basis(&tree("A!!"), Basis::Nand)
+                .unwrap()
+                .to_operator()
+                .to_string(),
+            "A"
+        );
+    }
+
+    #[test]
+    fn constant_formulas_need_a_constant_in_the_basis() {
+        for formula in ["0", "1", "10>", "01^!"] {
+            let operator = tree(formula);
+            for basis in [Basis::Nand, Basis::Nor, Basis::AndNot, Basis::OrNot] {
+                assert_eq!(to_basis(&operator, basis), None, "{} {:?}", formula, basis);
+            }
+            let converted = to_basis(&operator, Basis::ImpliesFalse)
+                .unwrap()
+                .to_operator();
+
+            assert!(Basis::ImpliesFalse.expresses(&converted), "{}", formula);
+            assert!(equivalent(&operator, &converted), "{}", formula);
+        }
+        assert_eq!(
+            to_basis(&tree("0"), Basis::ImpliesFalse)
+                .unwrap()
+                .to_operator()
+                .to_string(),
+            "0"
+        );
+        assert_eq!(
+            to_basis(&tree("1"), Basis::ImpliesFalse)
+                .unwrap()
+                .to_operator()
+                .to_string(),
+            "00>"
+        );
+    }
+
+    #[test]
+    fn sharing_is_reported() {
+        let xor = to_basis(&tree("AB^"), Basis::Nand).unwrap();
+        let report = size_report(&xor);
+
+        assert_eq!(report.gates, 4);
+        assert!(report.shared_nodes < report.nodes);
+        assert_eq!(report.to_string(), "16 nodes, 10 after sharing, 4 gates");
+        assert_eq!(
+            xor.to_string(),
+            "g1 = nand(A, B); g2 = nand(A, g1); g3 = nand(B, g1); nand(g2, g3)"
+        );
+        assert_eq!(xor.to_operator().size(), report.nodes);
+    }
+
+    #[test]
+    fn circuits_grow_with_the_formula() {
+        let operands: Vec<char> = ('A'..='T').collect();
+        let mut formula: String = operands.iter().collect();
+        formula.push_str(&"&".repeat(operands.len() - 1));
+        for basis in BASES {
+            let circuit = to_basis(&tree(&formula), basis).unwrap();
+            let report = size_report(&circuit);
+
+            assert!(report.gates <= 4 * operands.len(), "{:?}", basis);
+            assert!(report.shared_nodes <= 2 * report.gates + operands.len());
+        }
+        let nor = size_report(&to_basis(&tree(&formula), Basis::Nor).unwrap());
+        assert!(nor.nodes > 1_000_000);
+    }
+
+    #[test]
+    fn dropped_gates_are_left_out() {
+        let circuit = to_basis(&tree("A!!"), Basis::Nand).unwrap();
+
+        assert!(circuit.gates().is_empty());
+        assert_eq!(circuit.output(), Signal::Input('A'));
+        assert_eq!(circuit.to_string(), "A");
+        assert_eq!(
+            size_report(&circuit).to_string(),
+            "1 nodes, 1 after sharing, 0 gates"
+        );
+        assert_eq!(
+            to_basis(&tree("AB&!!"), Basis::AndNot).unwrap().to_string(),
+            "and(A, B)"
+        );
+    }
+
+    #[test]
+    fn bases_are_functionally_complete() {
+        for basis in BASES {
+            assert!(is_functionally_complete(basis.connectives()), "{:?}", basis);
+        }
+    }
+
+    #[test]
+    fn post_classes_find_incomplete_sets() {
+        assert_eq!(
+            post_classes(&[Connective::And, Connective::Or]),
+            vec![
+                PostClass::PreservesFalse,
+                PostClass::PreservesTrue,
+                PostClass::Monotone
+            ]
+        );
+        assert_eq!(
+            post_classes(&[Connective::Xor, Connective::Not]),
+            vec![PostClass::Affine]
+        );
+        assert_eq!(
+            post_classes(&[Connective::Implies]),
+            vec![PostClass::PreservesTrue]
+        );
+        assert!(!is_functionally_complete(&[
+            Connective::Not,
+            Connective::Equals
+        ]));
+        assert!(is_functionally_complete(&[
+            Connective::Xor,
+            Connective::And,
+            Connective::True
+        ]));
+    }
+}
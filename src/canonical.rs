@@ -0,0 +1,281 @@
+use std::fmt;
+
+use crate::incomplete::MAX_OPERANDS;
+use crate::operator::Operator;
+use crate::truth_table::TruthTable;
+
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum CanonicalForm {
+    SumOfProducts,
+    ProductOfSums,
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct CsvError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+fn check_terms(operands: &[char], terms: &[u64]) {
+    let len = 1u64 << operands.len();
+    if let Some(term) = terms.iter().find(|&&term| term >= len) {
+        panic!("Term {} out of range for {} operands", term, operands.len());
+    }
+}
+
+fn term_literals(operands: &[char], term: u64, positive_when: bool) -> Vec<Operator> {
+    let n = operands.len();
+    operands
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| Operator::literal(c, (term >> (n - 1 - i) & 1 == 1) == positive_when))
+        .collect()
+}
+
+pub fn minterms(operator: &Operator) -> Vec<u64> {
+    TruthTable::new(operator.clone(), &operator.operands()).minterms()
+}
+
+pub fn maxterms(operator: &Operator) -> Vec<u64> {
+    TruthTable::new(operator.clone(), &operator.operands()).maxterms()
+}
+
+pub fn sum_of_products(operands: &[char], minterms: &[u64]) -> Operator {
+    check_terms(operands, minterms);
+    Operator::disjunction(
+        minterms
+            .iter()
+            .map(|&m| Operator::conjunction(term_literals(operands, m, true))),
+    )
+}
+
+pub fn product_of_sums(operands: &[char], maxterms: &[u64]) -> Operator {
+    check_terms(operands, maxterms);
+    Operator::conjunction(
+        maxterms
+            .iter()
+            .map(|&m| Operator::disjunction(term_literals(operands, m, false))),
+    )
+}
+
+pub fn from_truth_vector(operands: &[char], vector: &[bool], form: CanonicalForm) -> Operator {
+    if vector.len() as u64 != 1u64 << operands.len() {
+        panic!(
+            "Truth vector for {} operands needs {} values",
+            operands.len(),
+            1u64 << operands.len()
+        );
+    }
+    let terms: Vec<u64> = (0..vector.len() as u64)
+        .filter(|&i| vector[i as usize] == (form == CanonicalForm::SumOfProducts))
+        .collect();
+    match form {
+        CanonicalForm::SumOfProducts => sum_of_products(operands, &terms),
+        CanonicalForm::ProductOfSums => product_of_sums(operands, &terms),
+    }
+}
+
+fn parse_bit(field: &str, line: usize) -> Result<bool, CsvError> {
+    match field {
+        "0" => Ok(false),
+        "1" => Ok(true),
+        _ => Err(CsvError {
+            line,
+            message: format!("Expected 0 or 1, found '{}'", field),
+        }),
+    }
+}
+
+// The header names the operand columns, the last column holds the result.
+// Rows may come in any order but every assignment has to be present once.
+pub fn from_csv(csv: &str, form: CanonicalForm) -> Result<Operator, CsvError> {
+    let mut lines = csv
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+    let (header_line, header) = lines.next().ok_or(CsvError {
+        line: 1,
+        message: "Missing header".to_string(),
+    })?;
+    let columns: Vec<&str> = header.split(',').map(|c| c.trim()).collect();
+    let mut operands = vec![];
+    for column in &columns[..columns.len() - 1] {
+        let mut chars = column.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_alphabetic() => {
+                let c = c.to_uppercase().next().unwrap();
+                if operands.contains(&c) {
+                    return Err(CsvError {
+                        line: header_line,
+                        message: format!("Duplicate operand '{}'", c),
+                    });
+                }
+                operands.push(c);
+            }
+            _ => {
+                return Err(CsvError {
+                    line: header_line,
+                    message: format!("Invalid operand name '{}'", column),
+                })
+            }
+        }
+    }
+    if operands.len() > MAX_OPERANDS {
+        return Err(CsvError {
+            line: header_line,
+            message: format!(
+                "Too many operands, {} found and at most {} supported",
+                operands.len(),
+                MAX_OPERANDS
+            ),
+        });
+    }
+    let mut vector: Vec<Option<bool>> = vec![None; 1 << operands.len()];
+    let mut last_line = header_line;
+    for (line, row) in lines {
+        last_line = line;
+        let fields: Vec<&str> = row.split(',').map(|f| f.trim()).collect();
+        if fields.len() != columns.len() {
+            return Err(CsvError {
+                line,
+                message: format!("Expected {} fields, found {}", columns.len(), fields.len()),
+            });
+        }
+        let mut index = 0;
+        for field in &fields[..operands.len()] {
+            index = index << 1 | parse_bit(field, line)? as usize;
+        }
+        let result = parse_bit(fields[operands.len()], line)?;
+        if vector[index].replace(result).is_some() {
+            return Err(CsvError {
+                line,
+                message: "Duplicate row".to_string(),
+            });
+        }
+    }
+    let missing = vector.iter().filter(|v| v.is_none()).count();
+    if missing > 0 {
+        return Err(CsvError {
+            line: last_line,
+            message: format!("{} rows are missing", missing),
+        });
+    }
+    let vector: Vec<bool> = vector.into_iter().map(|v| v.unwrap()).collect();
+    Ok(from_truth_vector(&operands, &vector, form))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::truth_table::tests::truth_tables_equal;
+
+    #[test]
+    fn minterms_and_maxterms_split_the_rows() {
+        let tree = Operator::from_formula("AB>").unwrap();
+
+        assert_eq!(minterms(&tree), vec![0, 1, 3]);
+        assert_eq!(maxterms(&tree), vec![2]);
+    }
+
+    #[test]
+    fn sum_of_products_works() {
+        let result = sum_of_products(&['A', 'B'], &[1, 2]);
+
+        assert_eq!(result.to_string(), "A!B&AB!&|");
+        truth_tables_equal("AB^", &result.to_string());
+    }
+
+    #[test]
+    fn product_of_sums_works() {
+        let result = product_of_sums(&['A', 'B'], &[2]);
+
+        assert_eq!(result.to_string(), "A!B|");
+        truth_tables_equal("AB>", &result.to_string());
+    }
+
+    #[test]
+    fn canonical_forms_round_trip() {
+        let original = "AB|C!&A=";
+        let tree = Operator::from_formula(original).unwrap();
+        let operands = tree.operands();
+        let sop = sum_of_products(&operands, &minterms(&tree));
+        let pos = product_of_sums(&operands, &maxterms(&tree));
+
+        truth_tables_equal(original, &sop.to_string());
+        truth_tables_equal(original, &pos.to_string());
+    }
+
+    #[test]
+    fn constant_functions_give_constants() {
+        assert_eq!(sum_of_products(&['A'], &[]), Operator::Constant(false));
+        assert_eq!(product_of_sums(&['A'], &[]), Operator::Constant(true));
+    }
+
+    #[test]
+    fn truth_vector_works() {
+        let vector = [false, true, true, true];
+        let sop = from_truth_vector(&['A', 'B'], &vector, CanonicalForm::SumOfProducts);
+        let pos = from_truth_vector(&['A', 'B'], &vector, CanonicalForm::ProductOfSums);
+
+        truth_tables_equal("AB|", &sop.to_string());
+        assert_eq!(pos.to_string(), "AB|");
+    }
+
+    #[test]
+    #[should_panic]
+    fn truth_vector_with_wrong_length_panics() {
+        from_truth_vector(&['A', 'B'], &[true], CanonicalForm::SumOfProducts);
+    }
+
+    #[test]
+    fn csv_table_works() {
+        let csv = "A,B,=\n1,1,0\n0,0,0\n0,1,1\n1,0,1\n";
+        let result = from_csv(csv, CanonicalForm::SumOfProducts).unwrap();
+
+        assert_eq!(result.to_string(), "A!B&AB!&|");
+    }
+
+    #[test]
+    fn csv_errors_have_line_numbers() {
+        let result = from_csv("A,B,=\n0,0,0\n0,1\n", CanonicalForm::SumOfProducts);
+        assert_eq!(result.unwrap_err().line, 3);
+
+        let result = from_csv("A,B,=\n0,0,0\n0,0,1\n", CanonicalForm::SumOfProducts);
+        assert_eq!(result.unwrap_err().message, "Duplicate row");
+
+        let result = from_csv("A,B,=\n0,0,0\n0,2,1\n", CanonicalForm::SumOfProducts);
+        assert_eq!(result.unwrap_err().line, 3);
+
+        let result = from_csv("A,B,=\n0,0,0\n", CanonicalForm::SumOfProducts);
+        assert_eq!(result.unwrap_err().message, "3 rows are missing");
+
+        let result = from_csv("AB,=\n", CanonicalForm::SumOfProducts);
+        assert_eq!(result.unwrap_err().line, 1);
+    }
+
+    #[test]
+    fn wide_csv_headers_are_rejected() {
+        let mut columns: Vec<String> = ('A'..='Z')
+            .chain("ΓΔΘΛ".chars())
+            .map(String::from)
+            .collect();
+        columns.push("=".to_string());
+        let csv = format!("\n{}\n", columns.join(","));
+        let error = from_csv(&csv, CanonicalForm::SumOfProducts).unwrap_err();
+
+        assert_eq!(error.line, 2);
+        assert_eq!(
+            error.message,
+            "Too many operands, 30 found and at most 24 supported"
+        );
+    }
+}
@@ -0,0 +1,344 @@
+use std::ops::Not;
+
+use crate::operator::Operator;
+
+// A variable index with a sign. Variables count from 0, DIMACS numbers them
+// from 1 and uses the sign of the number for the polarity.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, PartialOrd, Ord)]
+pub struct Literal {
+    variable: usize,
+    positive: bool,
+}
+
+impl Literal {
+    pub fn new(variable: usize, positive: bool) -> Literal {
+        Literal { variable, positive }
+    }
+
+    pub fn positive(variable: usize) -> Literal {
+        Literal::new(variable, true)
+    }
+
+    pub fn negative(variable: usize) -> Literal {
+        Literal::new(variable, false)
+    }
+
+    pub fn from_dimacs(number: i32) -> Literal {
+        if number == 0 {
+            panic!("0 is not a DIMACS literal");
+        }
+        Literal::new(number.unsigned_abs() as usize - 1, number > 0)
+    }
+
+    pub fn variable(self) -> usize {
+        self.variable
+    }
+
+    pub fn is_positive(self) -> bool {
+        self.positive
+    }
+
+    pub fn to_dimacs(self) -> i32 {
+        let number = self.variable as i32 + 1;
+        if self.positive {
+            number
+        } else {
+            -number
+        }
+    }
+}
+
+impl Not for Literal {
+    type Output = Literal;
+
+    fn not(self) -> Literal {
+        Literal::new(self.variable, !self.positive)
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Default)]
+pub struct Clause {
+    literals: Vec<Literal>,
+}
+
+impl Clause {
+    pub fn new(literals: Vec<Literal>) -> Clause {
+        Clause { literals }
+    }
+
+    pub fn from_dimacs(numbers: &[i32]) -> Clause {
+        Clause::new(numbers.iter().map(|&n| Literal::from_dimacs(n)).collect())
+    }
+
+    pub fn literals(&self) -> &[Literal] {
+        &self.literals
+    }
+
+    pub fn len(&self) -> usize {
+        self.literals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.literals.is_empty()
+    }
+
+    pub fn contains(&self, literal: Literal) -> bool {
+        self.literals.contains(&literal)
+    }
+
+    pub fn is_tautology(&self) -> bool {
+        self.literals.iter().any(|&l| self.contains(!l))
+    }
+
+    // Every literal of `self` is in `other`, so `other` is implied.
+    pub fn subsumes(&self, other: &Clause) -> bool {
+        self.literals.iter().all(|&l| other.contains(l))
+    }
+
+    // The resolvent on the only variable the clauses clash on, None when they
+    // clash on no variable or on several (the resolvent would be a tautology).
+    pub fn resolve(&self, other: &Clause) -> Option<Clause> {
+        let mut clashes = self.literals.iter().filter(|&&l| other.contains(!l));
+        let pivot = *clashes.next()?;
+        if clashes.next().is_some() {
+            return None;
+        }
+        let literals = self
+            .literals
+            .iter()
+            .chain(&other.literals)
+            .filter(|l| l.variable != pivot.variable)
+            .copied()
+            .collect();
+        Clause::new(literals).normalize()
+    }
+
+    // Sorted and without repeated literals, None for a tautology.
+    pub fn normalize(mut self) -> Option<Clause> {
+        self.literals.sort_unstable();
+        self.literals.dedup();
+        if self
+            .literals
+            .windows(2)
+            .any(|pair| pair[0].variable == pair[1].variable)
+        {
+            return None;
+        }
+        Some(self)
+    }
+}
+
+// A conjunction of clauses. The first variables stand for the operands, in
+// order, the ones above are auxiliary.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct Cnf {
+    operands: Vec<char>,
+    variables: usize,
+    clauses: Vec<Clause>,
+}
+
+impl Cnf {
+    pub fn new(operands: &[char]) -> Cnf {
+        Cnf {
+            operands: operands.to_vec(),
+            variables: operands.len(),
+            clauses: vec![],
+        }
+    }
+
+    // Room for `variables` variables, the ones past the operands are
+    // auxiliary.
+    pub fn with_variables(operands: &[char], variables: usize) -> Cnf {
+        if variables < operands.len() {
+            panic!(
+                "{} variables are too few for {} operands",
+                variables,
+                operands.len()
+            );
+        }
+        Cnf {
+            variables,
+            ..Cnf::new(operands)
+        }
+    }
+
+    // Equivalent clauses from distributing Or over And, exponential in the
+    // worst case.
+    pub fn from_operator(operator: &Operator) -> Cnf {
+        let mut cnf = Cnf::new(&operator.operands());
+        for clause in operator
+            .clone()
+            .to_negation_normal_form()
+            .to_conjunctive_normal_form()
+            .extract_clauses()
+        {
+            if clause.contains(&Operator::Constant(true)) {
+                continue;
+            }
+            let literals = clause
+                .iter()
+                .filter(|literal| **literal != Operator::Constant(false))
+                .map(|literal| {
+                    let (c, positive) = match literal {
+                        Operator::Operand(c) => (c, true),
+                        Operator::Not(inner) => match inner.as_ref() {
+                            Operator::Operand(c) => (c, false),
+                            _ => panic!("Clause literal {:?} is not an operand", literal),
+                        },
+                        _ => panic!("Clause literal {:?} is not an operand", literal),
+                    };
+                    Literal::new(cnf.variable(*c).unwrap(), positive)
+                })
+                .collect();
+            cnf.add_clause(Clause::new(literals));
+        }
+        cnf
+    }
+
+    pub fn operands(&self) -> &[char] {
+        &self.operands
+    }
+
+    pub fn variables(&self) -> usize {
+        self.variables
+    }
+
+    pub fn clauses(&self) -> &[Clause] {
+        &self.clauses
+    }
+
+    pub fn variable(&self, operand: char) -> Option<usize> {
+        self.operands.iter().position(|&c| c == operand)
+    }
+
+    pub fn fresh_variable(&mut self) -> usize {
+        self.variables += 1;
+        self.variables - 1
+    }
+
+    pub fn add_clause(&mut self, clause: Clause) {
+        if let Some(highest) = clause.literals.iter().map(|l| l.variable).max() {
+            self.variables = self.variables.max(highest + 1);
+        }
+        self.clauses.push(clause);
+    }
+
+    // Normalizes every clause, drops tautologies and repeated clauses.
+    pub fn normalize(mut self) -> Cnf {
+        let mut clauses: Vec<Clause> = vec![];
+        for clause in self.clauses.drain(..).filter_map(Clause::normalize) {
+            if !clauses.contains(&clause) {
+                clauses.push(clause);
+            }
+        }
+        self.clauses = clauses;
+        self
+    }
+
+    // `model[v]` is the value of variable `v`, auxiliary variables are
+    // dropped.
+    pub fn assignment(&self, model: &[bool]) -> Vec<(char, bool)> {
+        self.operands
+            .iter()
+            .zip(model)
+            .map(|(&c, &b)| (c, b))
+            .collect()
+    }
+
+    pub fn to_operator(&self) -> Operator {
+        let operand = |literal: &Literal| match self.operands.get(literal.variable) {
+            Some(&c) => Operator::literal(c, literal.positive),
+            None => panic!("Auxiliary variable {} has no operand", literal.variable),
+        };
+        Operator::conjunction(
+            self.clauses
+                .iter()
+                .map(|clause| Operator::disjunction(clause.literals.iter().map(operand))),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table_diff::equivalent;
+
+    #[test]
+    fn literals_convert_to_dimacs() {
+        assert_eq!(Literal::from_dimacs(3), Literal::positive(2));
+        assert_eq!(Literal::from_dimacs(-1), Literal::negative(0));
+        assert_eq!(Literal::negative(4).to_dimacs(), -5);
+        assert_eq!(!Literal::positive(1), Literal::negative(1));
+    }
+
+    #[test]
+    fn normalization_sorts_and_dedupes() {
+        let clause = Clause::from_dimacs(&[3, -1, 3, 2]).normalize().unwrap();
+
+        assert_eq!(clause, Clause::from_dimacs(&[-1, 2, 3]));
+    }
+
+    #[test]
+    fn tautologies_are_dropped() {
+        assert!(Clause::from_dimacs(&[1, 2, -1]).is_tautology());
+        assert_eq!(Clause::from_dimacs(&[1, 2, -1]).normalize(), None);
+
+        let mut cnf = Cnf::new(&['A', 'B']);
+        cnf.add_clause(Clause::from_dimacs(&[1, -1]));
+        cnf.add_clause(Clause::from_dimacs(&[2, 1]));
+        cnf.add_clause(Clause::from_dimacs(&[1, 2, 2]));
+        assert_eq!(cnf.normalize().clauses(), &[Clause::from_dimacs(&[1, 2])]);
+    }
+
+    #[test]
+    fn resolution_works() {
+        let a = Clause::from_dimacs(&[1, 2]);
+
+        assert_eq!(
+            a.resolve(&Clause::from_dimacs(&[-1, 3])),
+            Some(Clause::from_dimacs(&[2, 3]))
+        );
+        assert_eq!(a.resolve(&Clause::from_dimacs(&[-1, -2])), None);
+        assert_eq!(a.resolve(&Clause::from_dimacs(&[3])), None);
+        assert!(Clause::from_dimacs(&[2]).subsumes(&a));
+        assert!(!a.subsumes(&Clause::from_dimacs(&[2])));
+    }
+
+    #[test]
+    fn from_operator_distributes() {
+        let cnf = Cnf::from_operator(&Operator::from_formula("AB&C|").unwrap());
+
+        assert_eq!(cnf.variables(), 3);
+        assert_eq!(
+            cnf.clauses(),
+            &[Clause::from_dimacs(&[1, 3]), Clause::from_dimacs(&[2, 3])]
+        );
+    }
+
+    #[test]
+    fn round_trip_through_operator_is_equivalent() {
+        for formula in ["AB&C|", "AB^C>", "AB=!C&", "AB|A!B|&"] {
+            let operator = Operator::from_formula(formula).unwrap();
+            let cnf = Cnf::from_operator(&operator).normalize();
+
+            assert!(equivalent(&operator, &cnf.to_operator()), "{}", formula);
+        }
+    }
+
+    #[test]
+    fn adding_clauses_grows_the_variables() {
+        let mut cnf = Cnf::new(&['A']);
+        cnf.add_clause(Clause::from_dimacs(&[1, -4]));
+
+        assert_eq!(cnf.variables(), 4);
+        assert_eq!(cnf.fresh_variable(), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn auxiliary_variables_cannot_become_operators() {
+        let mut cnf = Cnf::new(&['A']);
+        cnf.add_clause(Clause::from_dimacs(&[2]));
+        cnf.to_operator();
+    }
+}
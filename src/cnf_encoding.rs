@@ -0,0 +1,310 @@
+use std::collections::HashMap;
+
+use crate::cnf::{Clause, Cnf, Literal};
+use crate::operator::Operator;
+
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub enum CnfEncoding {
+    Distributive,
+    #[default]
+    Tseitin,
+    PlaistedGreenbaum,
+}
+
+impl CnfEncoding {
+    pub fn encode(self, operator: &Operator) -> Cnf {
+        match self {
+            CnfEncoding::Distributive => Cnf::from_operator(operator),
+            CnfEncoding::Tseitin => tseitin(operator),
+            CnfEncoding::PlaistedGreenbaum => plaisted_greenbaum(operator),
+        }
+    }
+}
+
+// Which directions of `x <=> node` a definition needs: `x => node` when the
+// node occurs positively, `node => x` when it occurs negatively.
+#[derive(PartialEq, Debug, Clone, Copy)]
+struct Polarity {
+    positive: bool,
+    negative: bool,
+}
+
+const POSITIVE: Polarity = Polarity {
+    positive: true,
+    negative: false,
+};
+const BOTH: Polarity = Polarity {
+    positive: true,
+    negative: true,
+};
+const NONE: Polarity = Polarity {
+    positive: false,
+    negative: false,
+};
+
+impl Polarity {
+    fn flip(self) -> Polarity {
+        Polarity {
+            positive: self.negative,
+            negative: self.positive,
+        }
+    }
+
+    fn union(self, other: Polarity) -> Polarity {
+        Polarity {
+            positive: self.positive || other.positive,
+            negative: self.negative || other.negative,
+        }
+    }
+
+    fn without(self, other: Polarity) -> Polarity {
+        Polarity {
+            positive: self.positive && !other.positive,
+            negative: self.negative && !other.negative,
+        }
+    }
+}
+
+struct Encoder<'a> {
+    cnf: Cnf,
+    definitions: HashMap<&'a Operator, (Literal, Polarity)>,
+}
+
+impl<'a> Encoder<'a> {
+    fn new(operator: &Operator) -> Encoder<'a> {
+        Encoder {
+            cnf: Cnf::new(&operator.operands()),
+            definitions: HashMap::new(),
+        }
+    }
+
+    fn clause(&mut self, literals: &[Literal]) {
+        self.cnf.add_clause(Clause::new(literals.to_vec()));
+    }
+
+    // Literal standing for `node`, defined in the directions `polarity` asks
+    // for. A shared node gets the missing directions when it shows up again.
+    fn literal(&mut self, node: &'a Operator, polarity: Polarity) -> Literal {
+        match node {
+            Operator::Operand(c) => Literal::positive(self.cnf.variable(*c).unwrap()),
+            Operator::Not(a) => !self.literal(a, polarity.flip()),
+            _ => {
+                let defined = self.definitions.get(node).copied();
+                let needed = polarity.without(defined.map_or(NONE, |(_, p)| p));
+                if needed == NONE {
+                    return defined.unwrap().0;
+                }
+                let x = self.define(node, defined.map(|(x, _)| x), needed);
+                let polarity = defined.map_or(needed, |(_, p)| p.union(needed));
+                self.definitions.insert(node, (x, polarity));
+                x
+            }
+        }
+    }
+
+    // Clauses for the `needed` directions of `x <=> node`, `x` is None when
+    // the node has no variable yet.
+    fn define(&mut self, node: &'a Operator, x: Option<Literal>, needed: Polarity) -> Literal {
+        let variable = |encoder: &mut Encoder| {
+            x.unwrap_or_else(|| Literal::positive(encoder.cnf.fresh_variable()))
+        };
+        match node {
+            Operator::Constant(b) => {
+                let x = variable(self);
+                self.clause(&[if *b { x } else { !x }]);
+                x
+            }
+            Operator::And(a, b) => {
+                let (a, b) = (self.literal(a, needed), self.literal(b, needed));
+                let x = variable(self);
+                self.and_gate(x, a, b, needed);
+                x
+            }
+            Operator::Or(a, b) => {
+                let (a, b) = (self.literal(a, needed), self.literal(b, needed));
+                let x = variable(self);
+                self.and_gate(!x, !a, !b, needed.flip());
+                x
+            }
+            Operator::Implies(a, b) => {
+                let (a, b) = (self.literal(a, needed.flip()), self.literal(b, needed));
+                let x = variable(self);
+                self.and_gate(!x, a, !b, needed.flip());
+                x
+            }
+            Operator::Xor(a, b) => {
+                let (a, b) = (self.literal(a, BOTH), self.literal(b, BOTH));
+                let x = variable(self);
+                self.xor_gate(x, a, b, needed);
+                x
+            }
+            Operator::Equals(a, b) => {
+                let (a, b) = (self.literal(a, BOTH), self.literal(b, BOTH));
+                let x = variable(self);
+                self.xor_gate(x, a, !b, needed);
+                x
+            }
+            Operator::Operand(_) | Operator::Not(_) => unreachable!(),
+        }
+    }
+
+    // `x <=> a & b`, with `polarity` relative to `x`.
+    fn and_gate(&mut self, x: Literal, a: Literal, b: Literal, polarity: Polarity) {
+        if polarity.positive {
+            self.clause(&[!x, a]);
+            self.clause(&[!x, b]);
+        }
+        if polarity.negative {
+            self.clause(&[x, !a, !b]);
+        }
+    }
+
+    // `x <=> a ^ b`, with `polarity` relative to `x`.
+    fn xor_gate(&mut self, x: Literal, a: Literal, b: Literal, polarity: Polarity) {
+        if polarity.positive {
+            self.clause(&[!x, a, b]);
+            self.clause(&[!x, !a, !b]);
+        }
+        if polarity.negative {
+            self.clause(&[x, !a, b]);
+            self.clause(&[x, a, !b]);
+        }
+    }
+}
+
+// Equisatisfiable clauses with one auxiliary variable per distinct
+// connective, so the size stays linear in the formula.
+pub fn tseitin(operator: &Operator) -> Cnf {
+    let mut encoder = Encoder::new(operator);
+    let root = encoder.literal(operator, BOTH);
+    encoder.clause(&[root]);
+    encoder.cnf
+}
+
+// Like `tseitin` but only with the direction of each definition that the
+// polarity of the subformula needs, models of the result still map to models
+// of the formula.
+pub fn plaisted_greenbaum(operator: &Operator) -> Cnf {
+    let mut encoder = Encoder::new(operator);
+    let root = encoder.literal(operator, POSITIVE);
+    encoder.clause(&[root]);
+    encoder.cnf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sat::solve;
+
+    fn dimacs(cnf: &Cnf) -> Vec<Vec<i32>> {
+        cnf.clauses()
+            .iter()
+            .map(|clause| clause.literals().iter().map(|l| l.to_dimacs()).collect())
+            .collect()
+    }
+
+    fn tree(formula: &str) -> Operator {
+        Operator::from_formula(formula).unwrap()
+    }
+
+    fn xor_chain(n: usize) -> Operator {
+        let mut formula: String = ('A'..='Z').take(n + 1).collect();
+        formula.push_str(&"^".repeat(n));
+        tree(&formula)
+    }
+
+    #[test]
+    fn operands_come_first() {
+        let encoding = tseitin(&tree("AB&C|"));
+
+        assert_eq!(encoding.operands(), &['A', 'B', 'C']);
+        assert_eq!(encoding.variable('C'), Some(2));
+        assert_eq!(encoding.variables(), 5);
+        assert_eq!(dimacs(&encoding).last(), Some(&vec![5]));
+    }
+
+    #[test]
+    fn negations_do_not_need_variables() {
+        let encoding = tseitin(&tree("A!B!&!"));
+
+        assert_eq!(encoding.variables(), 3);
+        assert_eq!(
+            dimacs(&encoding),
+            vec![vec![-3, -1], vec![-3, -2], vec![3, 1, 2], vec![-3]]
+        );
+    }
+
+    #[test]
+    fn shared_subformulas_are_defined_once() {
+        let shared = tseitin(&tree("AB^AB^&"));
+
+        assert_eq!(shared.variables(), 4);
+    }
+
+    #[test]
+    fn tseitin_size_is_linear() {
+        let encoding = tseitin(&xor_chain(20));
+
+        assert_eq!(encoding.variables(), 41);
+        assert_eq!(encoding.clauses().len(), 4 * 20 + 1);
+    }
+
+    #[test]
+    fn distributive_encoding_uses_only_operands() {
+        let encoding = CnfEncoding::Distributive.encode(&tree("AB&C|"));
+
+        assert_eq!(encoding.variables(), 3);
+        assert_eq!(dimacs(&encoding), vec![vec![1, 3], vec![2, 3]]);
+    }
+
+    #[test]
+    fn encodings_agree_on_satisfiability() {
+        for formula in ["AB^", "AA^", "AB>BA>&AB=!&", "A1&", "A0&", "AB|C&A!&B!&"] {
+            let operator = tree(formula);
+            let distributive = CnfEncoding::Distributive.encode(&operator);
+            let expected = solve(&distributive).is_some();
+
+            for encoding in [CnfEncoding::Tseitin, CnfEncoding::PlaistedGreenbaum] {
+                let encoded = encoding.encode(&operator);
+                assert_eq!(solve(&encoded).is_some(), expected, "{}", formula);
+            }
+        }
+    }
+
+    #[test]
+    fn plaisted_greenbaum_keeps_one_direction() {
+        let encoding = plaisted_greenbaum(&tree("AB&C|"));
+
+        assert_eq!(encoding.variables(), 5);
+        assert_eq!(
+            dimacs(&encoding),
+            vec![vec![-4, 1], vec![-4, 2], vec![-5, 4, 3], vec![5]]
+        );
+        assert_eq!(
+            dimacs(&plaisted_greenbaum(&tree("AB&!"))),
+            vec![vec![3, -1, -2], vec![-3]]
+        );
+    }
+
+    #[test]
+    fn shared_subformulas_get_both_directions_when_needed() {
+        let encoding = plaisted_greenbaum(&tree("AB&AB&!|"));
+
+        assert_eq!(encoding.variables(), 4);
+        assert_eq!(encoding.clauses().len(), 5);
+    }
+
+    #[test]
+    fn plaisted_greenbaum_is_smaller() {
+        let operator = tree("AB&C|D>AB|C&D>&E!|");
+        let full = tseitin(&operator).clauses().len();
+        let reduced = plaisted_greenbaum(&operator).clauses().len();
+
+        assert!(
+            reduced * 3 < full * 2,
+            "{} is not smaller than {}",
+            reduced,
+            full
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use std::io::{self, Write};
+
+use crate::operator::Operator;
+use crate::truth_table::{RowOrder, TruthTable};
+
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub enum ColumnLabel {
+    #[default]
+    Infix,
+    Rpn,
+}
+
+impl ColumnLabel {
+    pub fn label(self, operator: &Operator) -> String {
+        match self {
+            ColumnLabel::Infix => operator.to_infix(),
+            ColumnLabel::Rpn => operator.to_string(),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct ColumnRow {
+    pub index: u64,
+    pub values: Vec<(char, bool)>,
+    pub results: Vec<bool>,
+}
+
+// A truth table with one result column per formula, all evaluated over the
+// union of their operands.
+#[derive(PartialEq, Debug, Clone)]
+pub struct ColumnTable {
+    operands: Vec<char>,
+    columns: Vec<TruthTable>,
+}
+
+impl ColumnTable {
+    pub fn new(formulas: Vec<Operator>) -> ColumnTable {
+        if formulas.is_empty() {
+            panic!("A column table needs at least one formula");
+        }
+        let mut operands: Vec<char> = vec![];
+        for formula in &formulas {
+            for c in formula.operands() {
+                if !operands.contains(&c) {
+                    operands.push(c);
+                }
+            }
+        }
+        ColumnTable::with_operands(formulas, operands)
+    }
+
+    fn with_operands(formulas: Vec<Operator>, operands: Vec<char>) -> ColumnTable {
+        let columns = formulas
+            .into_iter()
+            .map(|formula| TruthTable::new(formula, &operands))
+            .collect();
+        ColumnTable { operands, columns }
+    }
+
+    // Operand columns keep the order of the whole formula, not the order in
+    // which the subformulas mention them.
+    pub fn with_subformulas(operator: &Operator) -> ColumnTable {
+        let mut subformulas: Vec<Operator> = operator
+            .subformulas()
+            .into_iter()
+            .filter(|s| !matches!(s, Operator::Operand(_) | Operator::Constant(_)))
+            .cloned()
+            .collect();
+        if subformulas.is_empty() {
+            subformulas.push(operator.clone());
+        }
+        ColumnTable::with_operands(subformulas, operator.operands())
+    }
+
+    pub fn with_order(mut self, order: RowOrder) -> ColumnTable {
+        self.columns = self
+            .columns
+            .into_iter()
+            .map(|column| column.with_order(order))
+            .collect();
+        self
+    }
+
+    pub fn operands(&self) -> &[char] {
+        &self.operands
+    }
+
+    pub fn formulas(&self) -> Vec<&Operator> {
+        self.columns
+            .iter()
+            .map(|column| column.operator())
+            .collect()
+    }
+
+    pub fn labels(&self, label: ColumnLabel) -> Vec<String> {
+        self.formulas()
+            .into_iter()
+            .map(|f| label.label(f))
+            .collect()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = ColumnRow> + '_ {
+        let mut columns: Vec<_> = self.columns.iter().map(|column| column.rows()).collect();
+        std::iter::from_fn(move || {
+            let mut rows = columns.iter_mut().map(|rows| rows.next());
+            let first = rows.next()??;
+            let mut results = vec![first.result];
+            results.extend(rows.map(|row| row.expect("Columns have the same rows").result));
+            Some(ColumnRow {
+                index: first.index,
+                values: first.values,
+                results,
+            })
+        })
+    }
+}
+
+// Labels are escaped since infix labels can contain the `|` separator.
+pub fn write_columns(
+    table: &ColumnTable,
+    label: ColumnLabel,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    let labels: Vec<String> = table
+        .labels(label)
+        .iter()
+        .map(|l| l.replace('|', "\\|"))
+        .collect();
+    let widths: Vec<usize> = labels.iter().map(|l| l.chars().count()).collect();
+    let mut temp: String = table
+        .operands()
+        .iter()
+        .map(|c| format!("| {} ", c))
+        .collect();
+    for label in &labels {
+        temp.push_str(&format!("| {} ", label));
+    }
+    temp.push('|');
+    writeln!(out, "{}", temp)?;
+    let mut temp: String = table.operands().iter().map(|_| "|---").collect();
+    for width in &widths {
+        temp.push_str(&format!("|{}", "-".repeat(width + 2)));
+    }
+    temp.push('|');
+    writeln!(out, "{}", temp)?;
+    for row in table.rows() {
+        let mut temp: String = row
+            .values
+            .iter()
+            .map(|(_, b)| format!("| {} ", if *b { 1 } else { 0 }))
+            .collect();
+        for (result, width) in row.results.iter().zip(&widths) {
+            temp.push_str(&format!("| {:^width$} ", if *result { 1 } else { 0 }));
+        }
+        temp.push('|');
+        writeln!(out, "{}", temp)?;
+    }
+    Ok(())
+}
+
+pub fn print_columns(table: &ColumnTable, label: ColumnLabel) {
+    write_columns(table, label, &mut io::stdout().lock()).expect("Could not write truth table");
+}
+
+pub fn print_truth_table_with_subformulas(formula: &str, label: ColumnLabel) {
+    if let Some(operator) = Operator::from_formula(formula) {
+        print_columns(&ColumnTable::with_subformulas(&operator), label);
+    }
+}
+
+pub fn print_truth_tables(formulas: &[&str], label: ColumnLabel) {
+    let operators: Vec<Operator> = formulas
+        .iter()
+        .filter_map(|f| Operator::from_formula(f))
+        .collect();
+    if !operators.is_empty() {
+        print_columns(&ColumnTable::new(operators), label);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subformula_columns_are_in_evaluation_order() {
+        let tree = Operator::from_formula("AB&C!|").unwrap();
+        let table = ColumnTable::with_subformulas(&tree);
+
+        assert_eq!(
+            table.labels(ColumnLabel::Infix),
+            vec!["A & B", "!C", "(A & B) | !C"]
+        );
+        assert_eq!(table.labels(ColumnLabel::Rpn), vec!["AB&", "C!", "AB&C!|"]);
+    }
+
+    #[test]
+    fn printed_header_keeps_operand_order_and_escapes_labels() {
+        let tree = Operator::from_formula("ABC|&").unwrap();
+        let table = ColumnTable::with_subformulas(&tree);
+        let mut out = vec![];
+        write_columns(&table, ColumnLabel::Infix, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let mut lines = text.lines();
+
+        assert_eq!(table.operands(), &['A', 'B', 'C']);
+        assert_eq!(
+            lines.next(),
+            Some("| A | B | C | B \\| C | A & (B \\| C) |")
+        );
+        assert_eq!(lines.next(), Some("|---|---|---|--------|--------------|"));
+        assert_eq!(lines.nth(4), Some("| 1 | 0 | 0 |   0    |      0       |"));
+        assert_eq!(lines.last(), Some("| 1 | 1 | 1 |   1    |      1       |"));
+    }
+
+    #[test]
+    fn repeated_subformulas_get_one_column() {
+        let tree = Operator::from_formula("AB&AB&|").unwrap();
+        let table = ColumnTable::with_subformulas(&tree);
+
+        assert_eq!(table.labels(ColumnLabel::Rpn), vec!["AB&", "AB&AB&|"]);
+    }
+
+    #[test]
+    fn formula_without_connectives_is_its_own_column() {
+        let tree = Operator::from_formula("A").unwrap();
+        let table = ColumnTable::with_subformulas(&tree);
+
+        assert_eq!(table.labels(ColumnLabel::Rpn), vec!["A"]);
+    }
+
+    #[test]
+    fn rows_hold_a_result_per_column() {
+        let tree = Operator::from_formula("AB&!").unwrap();
+        let rows: Vec<Vec<bool>> = ColumnTable::with_subformulas(&tree)
+            .rows()
+            .map(|row| row.results)
+            .collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                vec![false, true],
+                vec![false, true],
+                vec![false, true],
+                vec![true, false],
+            ]
+        );
+    }
+
+    #[test]
+    fn formulas_share_operands() {
+        let table = ColumnTable::new(vec![
+            Operator::from_formula("AB>").unwrap(),
+            Operator::from_formula("BC|").unwrap(),
+        ]);
+        let rows: Vec<ColumnRow> = table.rows().collect();
+
+        assert_eq!(table.operands(), &['A', 'B', 'C']);
+        assert_eq!(rows.len(), 8);
+        assert_eq!(rows[4].results, vec![false, false]);
+        assert_eq!(rows[7].results, vec![true, true]);
+    }
+
+    #[test]
+    fn row_order_applies_to_every_column() {
+        let table = ColumnTable::new(vec![
+            Operator::from_formula("A").unwrap(),
+            Operator::from_formula("B").unwrap(),
+        ])
+        .with_order(RowOrder::ReverseBinary);
+        let first = table.rows().next().unwrap();
+
+        assert_eq!(first.index, 3);
+        assert_eq!(first.results, vec![true, true]);
+    }
+}
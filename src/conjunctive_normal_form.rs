@@ -1,33 +1,45 @@
 use itertools::Itertools;
 
+use crate::derivation::{map_children, Law, Trace};
 use crate::operator::Operator;
 
+// Distributes an Or at the root of `node` over an And operand, the left one
+// first. `Derivation` replays recorded steps with the same rule.
+pub(crate) fn distribution_rule(node: Operator) -> Result<(Law, Operator), Operator> {
+    let Operator::Or(a, b) = node else {
+        return Err(node);
+    };
+    match (*a, *b) {
+        (Operator::And(c, d), e) => Ok((
+            Law::Distributivity,
+            Operator::and(Operator::or(*c, e.clone()), Operator::or(*d, e)),
+        )),
+        (e, Operator::And(c, d)) => Ok((
+            Law::Distributivity,
+            Operator::and(Operator::or(e.clone(), *c), Operator::or(e, *d)),
+        )),
+        (a, b) => Err(Operator::or(a, b)),
+    }
+}
+
 impl Operator {
     pub fn to_conjunctive_normal_form(self) -> Operator {
-        match self {
-            Operator::Operand(_) => self,
-            Operator::Not(a) => Operator::not(a.to_conjunctive_normal_form()),
-            Operator::And(a, b) => {
-                let a = a.to_conjunctive_normal_form();
-                let b = b.to_conjunctive_normal_form();
-                Operator::And(Box::new(a), Box::new(b))
-            }
-            Operator::Or(a, b) => {
-                let a = a.to_conjunctive_normal_form();
-                let b = b.to_conjunctive_normal_form();
-                match (a, b) {
-                    (Operator::And(c, d), e) => Operator::and(
-                        Operator::or(*c, e.clone()).to_conjunctive_normal_form(),
-                        Operator::or(*d, e).to_conjunctive_normal_form(),
-                    ),
-                    (e, Operator::And(c, d)) => Operator::and(
-                        Operator::Or(Box::new(e.clone()), c.clone()).to_conjunctive_normal_form(),
-                        Operator::Or(Box::new(e), d.clone()).to_conjunctive_normal_form(),
-                    ),
-                    (e, f) => Operator::Or(Box::new(e), Box::new(f)),
-                }
+        self.conjunctive_normal_form_traced(&mut Trace::default())
+    }
+
+    // Operands are converted first, so an Or only distributes over operands
+    // that are already in conjunctive normal form.
+    pub(crate) fn conjunctive_normal_form_traced(self, trace: &mut Trace) -> Operator {
+        if let Operator::Xor(_, _) | Operator::Implies(_, _) | Operator::Equals(_, _) = self {
+            panic!("Operator {:?} not allowed. Expression must be in NNF", self);
+        }
+        let node = map_children(self, trace, Operator::conjunctive_normal_form_traced);
+        match distribution_rule(node) {
+            Ok((law, node)) => {
+                trace.record(law);
+                map_children(node, trace, Operator::conjunctive_normal_form_traced)
             }
-            a => panic!("Operator {:?} not allowed. Expression must be in NNF", a),
+            Err(node) => node,
         }
     }
 }
@@ -40,15 +52,14 @@ pub fn conjunctive_normal_form(formula: &str) -> String {
     let mut result = operands
         .unwrap()
         .to_negation_normal_form()
-        .to_conjunctive_normal_form().to_string();
+        .to_conjunctive_normal_form()
+        .to_string();
     let n = result.chars().filter(|c| *c == '&').count();
     result = result.chars().filter(|c| *c != '&').join("");
     result.push_str(&"&".repeat(n));
     result
 }
 
-
-
 #[cfg(test)]
 mod tests {
 
@@ -67,7 +78,7 @@ mod tests {
     fn conjunctive_normal_form_works_with_negated_and() {
         let original = "AB&!";
         let expected = "A!B!|";
-        let result = conjunctive_normal_form(&original);
+        let result = conjunctive_normal_form(original);
 
         assert_eq!(result, expected);
         truth_tables_equal(original, &result);
@@ -77,7 +88,7 @@ mod tests {
     fn conjunctive_normal_form_works_with_negated_or() {
         let original = "AB|!";
         let expected = "A!B!&";
-        let result = conjunctive_normal_form(&original);
+        let result = conjunctive_normal_form(original);
 
         assert_eq!(result, expected);
         truth_tables_equal(original, &result);
@@ -87,7 +98,7 @@ mod tests {
     fn conjunctive_normal_form_works_with_deep_and() {
         let original = "AB|C&D&";
         let expected = "AB|CD&&";
-        let result = conjunctive_normal_form(&original);
+        let result = conjunctive_normal_form(original);
 
         assert_eq!(result, expected);
         truth_tables_equal(original, &result);
@@ -97,7 +108,7 @@ mod tests {
     fn conjunctive_normal_form_works_with_1() {
         let original = "ABCD&|&";
         let expected = "ABC|BD|&&";
-        let result = conjunctive_normal_form(&original);
+        let result = conjunctive_normal_form(original);
 
         assert_eq!(result, expected);
         truth_tables_equal(original, &result);
@@ -107,7 +118,7 @@ mod tests {
     fn conjunctive_normal_form_works_with_many_ors() {
         let original = "AB|C|D|";
         let expected = "AB|C|D|";
-        let result = conjunctive_normal_form(&original);
+        let result = conjunctive_normal_form(original);
 
         assert_eq!(result, expected);
         truth_tables_equal(original, &result);
@@ -117,7 +128,7 @@ mod tests {
     fn conjunctive_normal_form_works_with_many_ands() {
         let original = "AB&C&D&";
         let expected = "ABCD&&&";
-        let result = conjunctive_normal_form(&original);
+        let result = conjunctive_normal_form(original);
 
         assert_eq!(result, expected);
         truth_tables_equal(original, &result);
@@ -127,7 +138,7 @@ mod tests {
     fn conjunctive_normal_form_works_complicated() {
         let original = "AB&!C!|";
         let expected = "A!B!|C!|";
-        let result = conjunctive_normal_form(&original);
+        let result = conjunctive_normal_form(original);
 
         assert_eq!(result, expected);
         truth_tables_equal(original, &result);
@@ -137,9 +148,9 @@ mod tests {
     fn last_subject_test() {
         let original = "AB|!C!&";
         let expected = "A!B!C!&&";
-        let result = conjunctive_normal_form(&original);
+        let result = conjunctive_normal_form(original);
 
-        truth_tables_equal(original, &expected);
+        truth_tables_equal(original, expected);
         truth_tables_equal(original, &result);
         assert_eq!(result, expected);
     }
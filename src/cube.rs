@@ -0,0 +1,151 @@
+use crate::operator::Operator;
+
+// A product of literals over a fixed list of operands. Bit `n - 1 - i` of
+// `mask` and `value` belongs to operand `i`, the same layout as the row index
+// of a truth table, so a cube contains a row when the masked bits agree.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, PartialOrd, Ord)]
+pub struct Cube {
+    pub mask: u64,
+    pub value: u64,
+}
+
+impl Cube {
+    pub fn new(mask: u64, value: u64) -> Cube {
+        Cube {
+            mask,
+            value: value & mask,
+        }
+    }
+
+    pub fn minterm(n: usize, index: u64) -> Cube {
+        Cube::new(full_mask(n), index)
+    }
+
+    pub fn contains(&self, index: u64) -> bool {
+        index & self.mask == self.value
+    }
+
+    pub fn literal_count(&self) -> u32 {
+        self.mask.count_ones()
+    }
+
+    pub fn literals(&self, operands: &[char]) -> Vec<(char, bool)> {
+        let n = operands.len();
+        operands
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.mask >> (n - 1 - i) & 1 == 1)
+            .map(|(i, &c)| (c, self.value >> (n - 1 - i) & 1 == 1))
+            .collect()
+    }
+}
+
+// A sum of products: the rows contained in any of the cubes.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Cover {
+    operands: Vec<char>,
+    cubes: Vec<Cube>,
+}
+
+impl Cover {
+    pub fn new(operands: &[char], cubes: Vec<Cube>) -> Cover {
+        Cover {
+            operands: operands.to_vec(),
+            cubes,
+        }
+    }
+
+    pub fn operands(&self) -> &[char] {
+        &self.operands
+    }
+
+    pub fn cubes(&self) -> &[Cube] {
+        &self.cubes
+    }
+
+    pub fn contains(&self, index: u64) -> bool {
+        self.cubes.iter().any(|cube| cube.contains(index))
+    }
+
+    pub fn literal_count(&self) -> u32 {
+        self.cubes.iter().map(|cube| cube.literal_count()).sum()
+    }
+
+    pub fn to_operator(&self) -> Operator {
+        Operator::disjunction(self.cubes.iter().map(|cube| {
+            Operator::conjunction(
+                cube.literals(&self.operands)
+                    .into_iter()
+                    .map(|(c, b)| Operator::literal(c, b)),
+            )
+        }))
+    }
+}
+
+pub fn full_mask(n: usize) -> u64 {
+    if n >= u64::BITS as usize {
+        u64::MAX
+    } else {
+        (1 << n) - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cube_contains_matching_rows() {
+        let cube = Cube::new(0b101, 0b100);
+
+        assert!(cube.contains(0b100));
+        assert!(cube.contains(0b110));
+        assert!(!cube.contains(0b101));
+        assert_eq!(cube.literal_count(), 2);
+    }
+
+    #[test]
+    fn cube_lists_literals_in_operand_order() {
+        let cube = Cube::new(0b101, 0b100);
+
+        assert_eq!(
+            cube.literals(&['A', 'B', 'C']),
+            vec![('A', true), ('C', false)]
+        );
+    }
+
+    #[test]
+    fn minterm_contains_only_its_row() {
+        let cube = Cube::minterm(3, 5);
+
+        assert_eq!(
+            (0..8).filter(|&i| cube.contains(i)).collect::<Vec<_>>(),
+            vec![5]
+        );
+    }
+
+    #[test]
+    fn cover_builds_a_sum_of_products() {
+        let cover = Cover::new(
+            &['A', 'B', 'C'],
+            vec![Cube::new(0b101, 0b100), Cube::new(0b010, 0b010)],
+        );
+
+        assert_eq!(cover.to_operator().to_string(), "AC!&B|");
+        assert_eq!(cover.literal_count(), 3);
+        assert!(cover.contains(0b010));
+        assert!(!cover.contains(0b001));
+    }
+
+    #[test]
+    fn empty_covers_are_constants() {
+        assert_eq!(
+            Cover::new(&['A'], vec![]).to_operator(),
+            Operator::Constant(false)
+        );
+        assert_eq!(
+            Cover::new(&['A'], vec![Cube::new(0, 0)]).to_operator(),
+            Operator::Constant(true)
+        );
+    }
+}
@@ -13,8 +13,8 @@ pub fn map(x: u16, y: u16) -> f64 {
 
             index += n * n * ((3 * rx as u16) ^ ry as u16);
 
-            if ry == false {
-                if rx == true {
+            if !ry {
+                if rx {
                     x = n - 1 - x;
                     y = n - 1 - y;
                 }
@@ -48,14 +48,14 @@ mod tests {
     #[test]
     fn test_map_with_something() {
         let result = map(1, 1);
-        assert!(result >= 0.0 && result <= 1.0);
+        assert!((0.0..=1.0).contains(&result));
     }
 
     #[test]
     fn test_map_with_big_number() {
         for i in 0..u16::MAX {
             let result = map(i, i);
-            assert!(result >= 0.0 && result <= 1.0);
+            assert!((0.0..=1.0).contains(&result));
         }
     }
 }
@@ -0,0 +1,427 @@
+use std::fmt;
+
+use crate::conjunctive_normal_form::distribution_rule;
+use crate::negation_normal_form::negation_rule;
+use crate::operator::Operator;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum Law {
+    DoubleNegation,
+    NegatedConstant,
+    DeMorgan,
+    NegatedImplication,
+    ImplicationElimination,
+    EquivalenceElimination,
+    ExclusiveOrElimination,
+    NegatedEquivalence,
+    NegatedExclusiveOr,
+    Distributivity,
+}
+
+impl fmt::Display for Law {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Law::DoubleNegation => "double negation",
+            Law::NegatedConstant => "negated constant",
+            Law::DeMorgan => "De Morgan",
+            Law::NegatedImplication => "negated implication",
+            Law::ImplicationElimination => "implication elimination",
+            Law::EquivalenceElimination => "equivalence elimination",
+            Law::ExclusiveOrElimination => "exclusive or elimination",
+            Law::NegatedEquivalence => "negated equivalence",
+            Law::NegatedExclusiveOr => "negated exclusive or",
+            Law::Distributivity => "distributivity",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// One rewrite. `path` leads from the root to the rewritten node, 0 for the
+// left or only operand and 1 for the right one.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Step {
+    pub law: Law,
+    pub path: Vec<u8>,
+}
+
+// Collects the steps of `to_negation_normal_form` and
+// `to_conjunctive_normal_form` while they run. The default trace records
+// nothing and does not track the path.
+#[derive(Default)]
+pub(crate) struct Trace {
+    path: Vec<u8>,
+    steps: Option<Vec<Step>>,
+}
+
+impl Trace {
+    fn recording() -> Trace {
+        Trace {
+            path: vec![],
+            steps: Some(vec![]),
+        }
+    }
+
+    pub(crate) fn record(&mut self, law: Law) {
+        if let Some(steps) = &mut self.steps {
+            steps.push(Step {
+                law,
+                path: self.path.clone(),
+            });
+        }
+    }
+
+    pub(crate) fn child<R>(&mut self, i: u8, f: impl FnOnce(&mut Trace) -> R) -> R {
+        if self.steps.is_none() {
+            return f(self);
+        }
+        self.path.push(i);
+        let result = f(self);
+        self.path.pop();
+        result
+    }
+}
+
+// Applies `f` to the operands of `node`, left to right.
+pub(crate) fn map_children(
+    node: Operator,
+    trace: &mut Trace,
+    f: fn(Operator, &mut Trace) -> Operator,
+) -> Operator {
+    fn two(
+        a: Box<Operator>,
+        b: Box<Operator>,
+        trace: &mut Trace,
+        f: fn(Operator, &mut Trace) -> Operator,
+    ) -> (Operator, Operator) {
+        let a = trace.child(0, |trace| f(*a, trace));
+        (a, trace.child(1, |trace| f(*b, trace)))
+    }
+    match node {
+        Operator::Not(a) => Operator::not(trace.child(0, |trace| f(*a, trace))),
+        Operator::And(a, b) => {
+            let (a, b) = two(a, b, trace, f);
+            Operator::and(a, b)
+        }
+        Operator::Or(a, b) => {
+            let (a, b) = two(a, b, trace, f);
+            Operator::or(a, b)
+        }
+        Operator::Xor(a, b) => {
+            let (a, b) = two(a, b, trace, f);
+            Operator::with_two('^', a, b)
+        }
+        Operator::Implies(a, b) => {
+            let (a, b) = two(a, b, trace, f);
+            Operator::with_two('>', a, b)
+        }
+        Operator::Equals(a, b) => {
+            let (a, b) = two(a, b, trace, f);
+            Operator::with_two('=', a, b)
+        }
+        Operator::Operand(_) | Operator::Constant(_) => node,
+    }
+}
+
+fn child_mut(node: &mut Operator, i: u8) -> &mut Operator {
+    match (node, i) {
+        (Operator::Not(a), 0) => a,
+        (
+            Operator::And(a, _)
+            | Operator::Or(a, _)
+            | Operator::Xor(a, _)
+            | Operator::Implies(a, _)
+            | Operator::Equals(a, _),
+            0,
+        ) => a,
+        (
+            Operator::And(_, b)
+            | Operator::Or(_, b)
+            | Operator::Xor(_, b)
+            | Operator::Implies(_, b)
+            | Operator::Equals(_, b),
+            1,
+        ) => b,
+        (node, _) => panic!("No operand {} in {}", i, node),
+    }
+}
+
+// Replays `step` on the whole formula with the rule the conversion used.
+fn apply(formula: &mut Operator, step: &Step) {
+    let node = step
+        .path
+        .iter()
+        .fold(formula, |node, &i| child_mut(node, i));
+    let rule = match step.law {
+        Law::Distributivity => distribution_rule,
+        _ => negation_rule,
+    };
+    *node = match rule(std::mem::replace(node, Operator::Constant(false))) {
+        Ok((law, after)) if law == step.law => after,
+        _ => panic!("{} does not apply at {:?}", step.law, step.path),
+    };
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum Notation {
+    #[default]
+    Infix,
+    Latex,
+}
+
+impl Notation {
+    fn render(self, operator: &Operator) -> String {
+        match self {
+            Notation::Infix => operator.to_infix(),
+            Notation::Latex => operator.to_latex(),
+        }
+    }
+}
+
+// A formula and the rewrites the conversions made on the way to its normal
+// form. Only the laws and their positions are kept, the intermediate
+// formulas are rebuilt one at a time when they are asked for.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Derivation {
+    start: Operator,
+    steps: Vec<Step>,
+    result: Operator,
+}
+
+impl Derivation {
+    pub fn negation_normal_form(operator: &Operator) -> Derivation {
+        let mut trace = Trace::recording();
+        let result = operator.clone().negation_normal_form_traced(&mut trace);
+        Derivation {
+            start: operator.clone(),
+            steps: trace.steps.unwrap_or_default(),
+            result,
+        }
+    }
+
+    // The steps to negation normal form followed by the ones of
+    // `to_conjunctive_normal_form`.
+    pub fn conjunctive_normal_form(operator: &Operator) -> Derivation {
+        let mut trace = Trace::recording();
+        let result = operator
+            .clone()
+            .negation_normal_form_traced(&mut trace)
+            .conjunctive_normal_form_traced(&mut trace);
+        Derivation {
+            start: operator.clone(),
+            steps: trace.steps.unwrap_or_default(),
+            result,
+        }
+    }
+
+    pub fn start(&self) -> &Operator {
+        &self.start
+    }
+
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    pub fn result(&self) -> &Operator {
+        &self.result
+    }
+
+    // The whole formula after each step.
+    pub fn formulas(&self) -> impl Iterator<Item = Operator> + '_ {
+        let mut current = self.start.clone();
+        self.steps.iter().map(move |step| {
+            apply(&mut current, step);
+            current.clone()
+        })
+    }
+
+    pub fn render(&self, notation: Notation) -> String {
+        let formulas: Vec<String> = self
+            .formulas()
+            .map(|formula| notation.render(&formula))
+            .collect();
+        match notation {
+            Notation::Infix => {
+                let width = formulas
+                    .iter()
+                    .map(|f| f.chars().count())
+                    .max()
+                    .unwrap_or(0);
+                let mut lines = vec![format!("  {}", notation.render(&self.start))];
+                for (formula, step) in formulas.iter().zip(&self.steps) {
+                    lines.push(format!("= {:width$}   [{}]", formula, step.law));
+                }
+                lines.join("\n")
+            }
+            Notation::Latex => {
+                let mut lines = vec![
+                    "\\begin{align*}".to_string(),
+                    format!("& {} \\\\", notation.render(&self.start)),
+                ];
+                for (formula, step) in formulas.iter().zip(&self.steps) {
+                    lines.push(format!(
+                        "&\\equiv {} && \\text{{{}}} \\\\",
+                        formula, step.law
+                    ));
+                }
+                lines.push("\\end{align*}".to_string());
+                lines.join("\n")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Derivation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.render(Notation::Infix))
+    }
+}
+
+pub fn explain_negation_normal_form(formula: &str, notation: Notation) -> String {
+    match Operator::from_formula(formula) {
+        Some(operator) => Derivation::negation_normal_form(&operator).render(notation),
+        None => String::new(),
+    }
+}
+
+pub fn explain_conjunctive_normal_form(formula: &str, notation: Notation) -> String {
+    match Operator::from_formula(formula) {
+        Some(operator) => Derivation::conjunctive_normal_form(&operator).render(notation),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::probability::Rng;
+    use crate::table_diff::equivalent;
+
+    fn tree(formula: &str) -> Operator {
+        Operator::from_formula(formula).unwrap()
+    }
+
+    fn laws(derivation: &Derivation) -> Vec<Law> {
+        derivation.steps().iter().map(|step| step.law).collect()
+    }
+
+    // A random formula in reverse polish notation over A, B, C, 0 and 1.
+    fn random_formula(rng: &mut Rng, depth: u32) -> String {
+        let pick = rng.next_u64();
+        if depth == 0 || pick.is_multiple_of(4) {
+            return ["A", "B", "C", "0", "1"][(pick >> 2) as usize % 5].to_string();
+        }
+        match (pick >> 2) % 6 {
+            0 => format!("{}!", random_formula(rng, depth - 1)),
+            n => format!(
+                "{}{}{}",
+                random_formula(rng, depth - 1),
+                random_formula(rng, depth - 1),
+                ['&', '|', '^', '>', '='][n as usize - 1]
+            ),
+        }
+    }
+
+    fn replayed(derivation: &Derivation) -> Operator {
+        let mut formula = derivation.start().clone();
+        for step in derivation.steps() {
+            apply(&mut formula, step);
+        }
+        formula
+    }
+
+    fn assert_matches_the_conversions(formula: &str) {
+        let operator = tree(formula);
+        let negation_normal_form = operator.clone().to_negation_normal_form();
+        let conjunctive_normal_form = negation_normal_form.clone().to_conjunctive_normal_form();
+
+        let derivation = Derivation::negation_normal_form(&operator);
+        assert_eq!(derivation.result(), &negation_normal_form, "{}", formula);
+        assert_eq!(replayed(&derivation), negation_normal_form, "{}", formula);
+
+        let derivation = Derivation::conjunctive_normal_form(&operator);
+        assert_eq!(derivation.result(), &conjunctive_normal_form, "{}", formula);
+        assert_eq!(
+            replayed(&derivation),
+            conjunctive_normal_form,
+            "{}",
+            formula
+        );
+    }
+
+    #[test]
+    fn negated_implication_is_explained() {
+        let derivation = Derivation::negation_normal_form(&tree("AB>!"));
+
+        assert_eq!(laws(&derivation), vec![Law::NegatedImplication]);
+        assert_eq!(derivation.result().to_string(), "AB!&");
+        assert_eq!(
+            derivation.to_string(),
+            "  !(A => B)\n\
+             = A & !B   [negated implication]"
+        );
+    }
+
+    #[test]
+    fn steps_are_chained() {
+        let derivation = Derivation::conjunctive_normal_form(&tree("AB=C>!"));
+        let mut current = derivation.start().clone();
+        for (formula, step) in derivation.formulas().zip(derivation.steps()) {
+            assert!(equivalent(&current, &formula), "{}", step.law);
+            current = formula;
+        }
+        assert_eq!(&current, derivation.result());
+    }
+
+    #[test]
+    fn results_match_the_conversions() {
+        for formula in [
+            "AB&!",
+            "AB=!",
+            "AB^C|!",
+            "AB>C=",
+            "A!!0!&B|",
+            "AB&C|D!&!",
+            "CA^!A=!",
+            "C0A=^!",
+            "B0A1=|=!",
+        ] {
+            assert_matches_the_conversions(formula);
+        }
+    }
+
+    #[test]
+    fn random_results_match_the_conversions() {
+        let mut rng = Rng(46);
+        for _ in 0..300 {
+            assert_matches_the_conversions(&random_formula(&mut rng, 3));
+        }
+    }
+
+    #[test]
+    fn distributivity_is_applied_innermost_first() {
+        let derivation = Derivation::conjunctive_normal_form(&tree("AB&C|"));
+
+        assert_eq!(laws(&derivation), vec![Law::Distributivity]);
+        assert_eq!(derivation.result().to_infix(), "(A | C) & (B | C)");
+    }
+
+    #[test]
+    fn normal_forms_need_no_steps() {
+        let derivation = Derivation::conjunctive_normal_form(&tree("AB|C!&"));
+
+        assert!(derivation.steps().is_empty());
+        assert_eq!(derivation.to_string(), "  (A | B) & !C");
+    }
+
+    #[test]
+    fn latex_rendering_works() {
+        assert_eq!(
+            explain_negation_normal_form("AB|!", Notation::Latex),
+            "\\begin{align*}\n\
+             & \\lnot (A \\lor B) \\\\\n\
+             &\\equiv \\lnot A \\land \\lnot B && \\text{De Morgan} \\\\\n\
+             \\end{align*}"
+        );
+        assert_eq!(explain_conjunctive_normal_form("", Notation::Infix), "");
+    }
+}
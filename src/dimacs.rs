@@ -0,0 +1,375 @@
+use std::fmt;
+use std::io::{self, Write};
+
+use crate::cnf::{Clause, Cnf, Literal};
+use crate::cnf_encoding::CnfEncoding;
+use crate::operator::Operator;
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct DimacsError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for DimacsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for DimacsError {}
+
+fn error(line: usize, message: String) -> DimacsError {
+    DimacsError { line, message }
+}
+
+fn parse_header(line: usize, text: &str) -> Result<(usize, usize), DimacsError> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let ["p", "cnf", variables, clauses] = words[..] else {
+        return Err(error(
+            line,
+            format!("Expected 'p cnf <variables> <clauses>', found '{}'", text),
+        ));
+    };
+    let count = |word: &str, what: &str| {
+        word.parse::<usize>()
+            .map_err(|_| error(line, format!("Invalid {} count '{}'", what, word)))
+    };
+    let variables = count(variables, "variable")?;
+    if variables > i32::MAX as usize {
+        return Err(error(
+            line,
+            format!(
+                "Too many variables, {} found and at most {} supported",
+                variables,
+                i32::MAX
+            ),
+        ));
+    }
+    Ok((variables, count(clauses, "clause")?))
+}
+
+// The operand of a `c variable <n> is <name>` comment as written by
+// `write_dimacs`. Names are single letters, uppercased like the operands of a
+// formula.
+fn parse_name(line: usize, comment: &str) -> Result<Option<(usize, char)>, DimacsError> {
+    let words: Vec<&str> = comment.split_whitespace().collect();
+    let ["c", "variable", number, "is", name] = words[..] else {
+        return Ok(None);
+    };
+    let Ok(number) = number.parse() else {
+        return Ok(None);
+    };
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_alphabetic() => {
+            Ok(Some((number, c.to_uppercase().next().unwrap())))
+        }
+        _ => Err(error(line, format!("Invalid variable name '{}'", name))),
+    }
+}
+
+// Reads a DIMACS CNF file. Clauses may span lines and end with 0, a line
+// starting with `%` ends the file as in the SATLIB benchmarks. Variable names
+// in the comments `write_dimacs` emits become the operands.
+pub fn parse_dimacs(text: &str) -> Result<Cnf, DimacsError> {
+    let mut names: Vec<(usize, char)> = vec![];
+    let mut header: Option<(usize, usize)> = None;
+    let mut clauses: Vec<Clause> = vec![];
+    let mut literals: Vec<Literal> = vec![];
+    let mut last = 0;
+    for (number, line) in text.lines().enumerate() {
+        let number = number + 1;
+        let line = line.trim();
+        last = number;
+        if line.is_empty() {
+            continue;
+        }
+        if line.starts_with('%') {
+            break;
+        }
+        if line.starts_with('c') {
+            if let Some((variable, name)) = parse_name(number, line)? {
+                if names.iter().any(|&(_, c)| c == name) {
+                    return Err(error(number, format!("Duplicate variable name '{}'", name)));
+                }
+                if names.iter().any(|&(v, _)| v == variable) {
+                    return Err(error(
+                        number,
+                        format!("Variable {} is named twice", variable),
+                    ));
+                }
+                names.push((variable, name));
+            }
+            continue;
+        }
+        if line.starts_with('p') {
+            if header.is_some() {
+                return Err(error(number, "Duplicate 'p cnf' header".to_string()));
+            }
+            header = Some(parse_header(number, line)?);
+            continue;
+        }
+        let Some((variables, count)) = header else {
+            return Err(error(
+                number,
+                "Clause before the 'p cnf' header".to_string(),
+            ));
+        };
+        for word in line.split_whitespace() {
+            let literal: i32 = word
+                .parse()
+                .map_err(|_| error(number, format!("Invalid literal '{}'", word)))?;
+            if literal == 0 {
+                if clauses.len() == count {
+                    return Err(error(
+                        number,
+                        format!("More clauses than the {} in the header", count),
+                    ));
+                }
+                clauses.push(Clause::new(std::mem::take(&mut literals)));
+                continue;
+            }
+            if literal.unsigned_abs() as usize > variables {
+                return Err(error(
+                    number,
+                    format!(
+                        "Literal {} out of range for {} variables",
+                        literal, variables
+                    ),
+                ));
+            }
+            literals.push(Literal::from_dimacs(literal));
+        }
+    }
+    let Some((variables, count)) = header else {
+        return Err(error(last, "Missing 'p cnf' header".to_string()));
+    };
+    if !literals.is_empty() {
+        return Err(error(
+            last,
+            "Last clause is not terminated by 0".to_string(),
+        ));
+    }
+    if clauses.len() != count {
+        return Err(error(
+            last,
+            format!("Expected {} clauses, found {}", count, clauses.len()),
+        ));
+    }
+    names.sort_unstable();
+    let operands: Vec<char> = names
+        .iter()
+        .enumerate()
+        .take_while(|(i, (number, _))| *number == i + 1 && *number <= variables)
+        .map(|(_, (_, c))| *c)
+        .collect();
+    let mut cnf = Cnf::with_variables(&operands, variables);
+    for clause in clauses {
+        cnf.add_clause(clause);
+    }
+    Ok(cnf)
+}
+
+pub fn write_dimacs(cnf: &Cnf, out: &mut dyn Write) -> io::Result<()> {
+    for (i, c) in cnf.operands().iter().enumerate() {
+        writeln!(out, "c variable {} is {}", i + 1, c)?;
+    }
+    let auxiliary = cnf.operands().len() + 1;
+    if auxiliary <= cnf.variables() {
+        writeln!(
+            out,
+            "c variables {} to {} are auxiliary",
+            auxiliary,
+            cnf.variables()
+        )?;
+    }
+    writeln!(out, "p cnf {} {}", cnf.variables(), cnf.clauses().len())?;
+    for clause in cnf.clauses() {
+        for literal in clause.literals() {
+            write!(out, "{} ", literal.to_dimacs())?;
+        }
+        writeln!(out, "0")?;
+    }
+    Ok(())
+}
+
+pub fn to_dimacs(operator: &Operator, encoding: CnfEncoding) -> String {
+    let mut out = vec![];
+    write_dimacs(&encoding.encode(operator), &mut out).expect("Writing to a Vec never fails");
+    String::from_utf8(out).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sat::solve;
+
+    fn tree(formula: &str) -> Operator {
+        Operator::from_formula(formula).unwrap()
+    }
+
+    fn message(text: &str) -> (usize, String) {
+        let error = parse_dimacs(text).unwrap_err();
+        (error.line, error.message)
+    }
+
+    #[test]
+    fn benchmark_style_files_are_read() {
+        let cnf = parse_dimacs(
+            "c a small instance\n\
+             c\n\
+             p cnf 3 2\n\
+             1 -3 0\n\
+             2 3\n\
+             -1 0\n\
+             %\n\
+             0\n",
+        )
+        .unwrap();
+
+        assert_eq!(cnf.variables(), 3);
+        assert!(cnf.operands().is_empty());
+        assert_eq!(
+            cnf.clauses(),
+            &[
+                Clause::from_dimacs(&[1, -3]),
+                Clause::from_dimacs(&[2, 3, -1])
+            ]
+        );
+        assert!(solve(&cnf).is_some());
+    }
+
+    #[test]
+    fn header_is_validated() {
+        assert_eq!(
+            message("1 2 0\n"),
+            (1, "Clause before the 'p cnf' header".to_string())
+        );
+        assert_eq!(
+            message("c\np dnf 2 1\n"),
+            (
+                2,
+                "Expected 'p cnf <variables> <clauses>', found 'p dnf 2 1'".to_string()
+            )
+        );
+        for header in ["pcnf cnf 3 2", "problem cnf 3 2"] {
+            assert_eq!(
+                message(header),
+                (
+                    1,
+                    format!("Expected 'p cnf <variables> <clauses>', found '{}'", header)
+                )
+            );
+        }
+        assert_eq!(
+            message("p cnf 99999999999 1\n"),
+            (
+                1,
+                "Too many variables, 99999999999 found and at most 2147483647 supported"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            message("p cnf x 1\n"),
+            (1, "Invalid variable count 'x'".to_string())
+        );
+        assert_eq!(
+            message("p cnf 2 1\np cnf 2 1\n"),
+            (2, "Duplicate 'p cnf' header".to_string())
+        );
+        assert_eq!(
+            message("c only comments\n"),
+            (1, "Missing 'p cnf' header".to_string())
+        );
+    }
+
+    #[test]
+    fn clauses_are_validated() {
+        assert_eq!(
+            message("p cnf 2 1\n1 3 0\n"),
+            (2, "Literal 3 out of range for 2 variables".to_string())
+        );
+        assert_eq!(
+            message("p cnf 2 1\n1 a 0\n"),
+            (2, "Invalid literal 'a'".to_string())
+        );
+        assert_eq!(
+            message("p cnf 2 1\n1 0\n2 0\n"),
+            (3, "More clauses than the 1 in the header".to_string())
+        );
+        assert_eq!(
+            message("p cnf 2 2\n1 0\n\n"),
+            (3, "Expected 2 clauses, found 1".to_string())
+        );
+        assert_eq!(
+            message("p cnf 2 1\n1 2\n"),
+            (2, "Last clause is not terminated by 0".to_string())
+        );
+    }
+
+    #[test]
+    fn names_must_be_unique_letters() {
+        assert_eq!(
+            parse_dimacs("c variable 1 is a\nc variable 2 is B\np cnf 2 1\n1 2 0\n")
+                .unwrap()
+                .operands(),
+            &['A', 'B']
+        );
+        assert_eq!(
+            message("c variable 1 is 1\np cnf 1 0\n"),
+            (1, "Invalid variable name '1'".to_string())
+        );
+        assert_eq!(
+            message("c variable 1 is AB\np cnf 1 0\n"),
+            (1, "Invalid variable name 'AB'".to_string())
+        );
+        assert_eq!(
+            message("c variable 1 is A\nc variable 2 is a\np cnf 2 0\n"),
+            (2, "Duplicate variable name 'A'".to_string())
+        );
+        assert_eq!(
+            message("c variable 1 is A\nc variable 1 is B\np cnf 2 0\n"),
+            (2, "Variable 1 is named twice".to_string())
+        );
+        assert!(parse_dimacs("c variable names follow\np cnf 1 0\n").is_ok());
+    }
+
+    #[test]
+    fn writer_names_the_variables() {
+        assert_eq!(
+            to_dimacs(&tree("AB&!"), CnfEncoding::PlaistedGreenbaum),
+            "c variable 1 is A\n\
+             c variable 2 is B\n\
+             c variables 3 to 3 are auxiliary\n\
+             p cnf 3 2\n\
+             3 -1 -2 0\n\
+             -3 0\n"
+        );
+        assert_eq!(
+            to_dimacs(&tree("AB|C&"), CnfEncoding::Distributive),
+            "c variable 1 is A\n\
+             c variable 2 is B\n\
+             c variable 3 is C\n\
+             p cnf 3 2\n\
+             1 2 0\n\
+             3 0\n"
+        );
+    }
+
+    #[test]
+    fn round_trip_keeps_clauses_and_names() {
+        for formula in ["AB^C>", "AB=C!&D|", "AA!&"] {
+            for encoding in [
+                CnfEncoding::Distributive,
+                CnfEncoding::Tseitin,
+                CnfEncoding::PlaistedGreenbaum,
+            ] {
+                let cnf = encoding.encode(&tree(formula));
+                let parsed = parse_dimacs(&to_dimacs(&tree(formula), encoding)).unwrap();
+
+                assert_eq!(parsed, cnf, "{} {:?}", formula, encoding);
+            }
+        }
+    }
+}
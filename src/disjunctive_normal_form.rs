@@ -0,0 +1,159 @@
+use itertools::Itertools;
+
+use crate::operator::Operator;
+
+fn literal(operator: &Operator) -> (char, bool) {
+    match operator {
+        Operator::Operand(c) => (*c, true),
+        Operator::Not(inner) => match inner.as_ref() {
+            Operator::Operand(c) => (*c, false),
+            _ => panic!(
+                "Operator {:?} not allowed. Expression must be in NNF",
+                operator
+            ),
+        },
+        _ => panic!(
+            "Operator {:?} not allowed. Expression must be in NNF",
+            operator
+        ),
+    }
+}
+
+fn nnf_cubes(operator: &Operator) -> Vec<Vec<(char, bool)>> {
+    match operator {
+        Operator::Constant(true) => vec![vec![]],
+        Operator::Constant(false) => vec![],
+        Operator::Or(a, b) => {
+            let mut cubes = nnf_cubes(a);
+            cubes.append(&mut nnf_cubes(b));
+            cubes
+        }
+        Operator::And(a, b) => {
+            let right = nnf_cubes(b);
+            nnf_cubes(a)
+                .into_iter()
+                .cartesian_product(right)
+                .map(|(mut left, mut right)| {
+                    left.append(&mut right);
+                    left
+                })
+                .collect()
+        }
+        _ => vec![vec![literal(operator)]],
+    }
+}
+
+impl Operator {
+    // Cubes of the disjunctive normal form. Repeated literals are merged,
+    // cubes holding a literal and its negation are dropped and cubes with the
+    // same literals are kept once. The empty cube is true and absorbs all the
+    // others.
+    pub fn disjunctive_cubes(&self) -> Vec<Vec<(char, bool)>> {
+        let mut cubes: Vec<Vec<(char, bool)>> = vec![];
+        for cube in nnf_cubes(&self.clone().to_negation_normal_form()) {
+            let cube: Vec<(char, bool)> = cube.into_iter().unique().collect();
+            if cube.is_empty() {
+                return vec![cube];
+            }
+            if cube.iter().any(|(c, b)| cube.contains(&(*c, !b)))
+                || cubes.iter().any(|other| {
+                    other.len() == cube.len() && cube.iter().all(|l| other.contains(l))
+                })
+            {
+                continue;
+            }
+            cubes.push(cube);
+        }
+        cubes
+    }
+
+    pub fn to_disjunctive_normal_form(self) -> Operator {
+        Operator::disjunction(self.disjunctive_cubes().into_iter().map(|cube| {
+            Operator::conjunction(cube.into_iter().map(|(c, b)| Operator::literal(c, b)))
+        }))
+    }
+}
+
+pub fn disjunctive_normal_form(formula: &str) -> String {
+    let operands = Operator::from_formula(formula);
+    if operands.is_none() {
+        return String::new();
+    }
+    let mut result = operands.unwrap().to_disjunctive_normal_form().to_string();
+    let n = result.chars().filter(|c| *c == '|').count();
+    result = result.chars().filter(|c| *c != '|').join("");
+    result.push_str(&"|".repeat(n));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::truth_table::tests::truth_tables_equal;
+
+    use super::*;
+
+    #[test]
+    fn disjunctive_normal_form_works_with_empty_string() {
+        assert_eq!(disjunctive_normal_form(""), "");
+    }
+
+    #[test]
+    fn disjunctive_normal_form_distributes_and_over_or() {
+        let original = "AB|C&";
+        let expected = "AC&BC&|";
+        let result = disjunctive_normal_form(original);
+
+        assert_eq!(result, expected);
+        truth_tables_equal(original, &result);
+    }
+
+    #[test]
+    fn disjunctive_normal_form_works_with_many_cubes() {
+        let original = "AB|CD|&";
+        let expected = "AC&AD&BC&BD&|||";
+        let result = disjunctive_normal_form(original);
+
+        assert_eq!(result, expected);
+        truth_tables_equal(original, &result);
+    }
+
+    #[test]
+    fn identical_cubes_are_merged() {
+        let original = "AB&BA&|A&";
+        let result = disjunctive_normal_form(original);
+
+        assert_eq!(result, "AB&");
+        truth_tables_equal(original, &result);
+    }
+
+    #[test]
+    fn contradictory_cubes_are_dropped() {
+        let original = "AA!|AB|&";
+        let tree = Operator::from_formula(original).unwrap();
+
+        assert_eq!(
+            tree.disjunctive_cubes(),
+            vec![
+                vec![('A', true)],
+                vec![('A', true), ('B', true)],
+                vec![('A', false), ('B', true)]
+            ]
+        );
+        truth_tables_equal(original, &disjunctive_normal_form(original));
+    }
+
+    #[test]
+    fn contradictions_and_tautologies_become_constants() {
+        assert_eq!(disjunctive_normal_form("AA!&"), "0");
+        assert_eq!(disjunctive_normal_form("1A|"), "1");
+        assert_eq!(disjunctive_normal_form("AB&1|C|"), "1");
+        assert_eq!(disjunctive_normal_form("A1&"), "A");
+    }
+
+    #[test]
+    fn disjunctive_normal_form_works_with_other_operators() {
+        for original in ["AB^", "AB=C>", "AB>C^D&"] {
+            truth_tables_equal(original, &disjunctive_normal_form(original));
+        }
+    }
+}
@@ -0,0 +1,594 @@
+use std::fmt;
+
+use crate::cube::{Cover, Cube};
+use crate::incomplete::IncompleteFunction;
+use crate::operator::Operator;
+
+// Default input names of PLA files without an .ilb line: A to Z, then the 24
+// Greek capitals, which formulas also accept as operands. The unassigned code
+// point between Ρ and Σ is skipped.
+fn default_operands(n: usize) -> Option<Vec<char>> {
+    let names: Vec<char> = ('A'..='Z')
+        .chain(('Α'..='Ω').filter(|c| c.is_alphabetic()))
+        .take(n)
+        .collect();
+    (names.len() == n).then_some(names)
+}
+
+fn intersects(a: &Cube, b: &Cube) -> bool {
+    (a.value ^ b.value) & a.mask & b.mask == 0
+}
+
+fn covers(a: &Cube, b: &Cube) -> bool {
+    a.mask & !b.mask == 0 && (a.value ^ b.value) & a.mask == 0
+}
+
+// Cubes of `cover` restricted to `by`, with the literals of `by` removed.
+fn cofactor(cover: &[Cube], by: &Cube) -> Vec<Cube> {
+    cover
+        .iter()
+        .filter(|cube| intersects(cube, by))
+        .map(|cube| Cube::new(cube.mask & !by.mask, cube.value))
+        .collect()
+}
+
+// Most frequent variable, preferring the ones that appear in both polarities.
+fn splitting_bit(cover: &[Cube], binate: u64) -> u64 {
+    let candidates = if binate != 0 {
+        binate
+    } else {
+        cover.iter().fold(0, |acc, cube| acc | cube.mask)
+    };
+    (0..64)
+        .map(|i| 1u64 << i)
+        .filter(|bit| candidates & bit != 0)
+        .max_by_key(|bit| cover.iter().filter(|cube| cube.mask & bit != 0).count())
+        .unwrap()
+}
+
+fn binate_bits(cover: &[Cube]) -> u64 {
+    let positive = cover
+        .iter()
+        .fold(0, |acc, cube| acc | (cube.mask & cube.value));
+    let negative = cover
+        .iter()
+        .fold(0, |acc, cube| acc | (cube.mask & !cube.value));
+    positive & negative
+}
+
+fn is_tautology(cover: &[Cube]) -> bool {
+    if cover.iter().any(|cube| cube.mask == 0) {
+        return true;
+    }
+    let binate = binate_bits(cover);
+    // A unate cover without the universal cube always misses a row.
+    if cover.is_empty() || binate == 0 {
+        return false;
+    }
+    let bit = splitting_bit(cover, binate);
+    is_tautology(&cofactor(cover, &Cube::new(bit, bit)))
+        && is_tautology(&cofactor(cover, &Cube::new(bit, 0)))
+}
+
+fn complement(cover: &[Cube]) -> Vec<Cube> {
+    if cover.is_empty() {
+        return vec![Cube::new(0, 0)];
+    }
+    if cover.iter().any(|cube| cube.mask == 0) {
+        return vec![];
+    }
+    if let [cube] = cover {
+        return (0..64)
+            .map(|i| 1u64 << i)
+            .filter(|bit| cube.mask & bit != 0)
+            .map(|bit| Cube::new(bit, !cube.value))
+            .collect();
+    }
+    let bit = splitting_bit(cover, binate_bits(cover));
+    let ones: Vec<Cube> = complement(&cofactor(cover, &Cube::new(bit, bit)));
+    let zeros: Vec<Cube> = complement(&cofactor(cover, &Cube::new(bit, 0)));
+    let mut result = vec![];
+    for cube in &ones {
+        if zeros.contains(cube) {
+            result.push(*cube);
+        } else {
+            result.push(Cube::new(cube.mask | bit, cube.value | bit));
+        }
+    }
+    for cube in zeros.iter().filter(|cube| !ones.contains(cube)) {
+        result.push(Cube::new(cube.mask | bit, cube.value));
+    }
+    result
+}
+
+fn supercube(cubes: &[Cube]) -> Cube {
+    let common = cubes.iter().fold(u64::MAX, |acc, cube| acc & cube.mask);
+    let ones = cubes.iter().fold(u64::MAX, |acc, cube| acc & cube.value);
+    let zeros = cubes.iter().fold(u64::MAX, |acc, cube| acc & !cube.value);
+    Cube::new(common & (ones | zeros), ones)
+}
+
+fn without_contained(cubes: Vec<Cube>) -> Vec<Cube> {
+    let mut kept: Vec<Cube> = vec![];
+    for cube in cubes {
+        if !kept.iter().any(|k| covers(k, &cube)) {
+            kept.retain(|k| !covers(&cube, k));
+            kept.push(cube);
+        }
+    }
+    kept
+}
+
+// Drops literals from every cube as long as it stays clear of the off-set,
+// largest cubes first so smaller ones are likely to end up inside them.
+fn expand(mut cubes: Vec<Cube>, off: &[Cube]) -> Vec<Cube> {
+    cubes.sort_by_key(|cube| cube.literal_count());
+    let mut expanded: Vec<Cube> = vec![];
+    for mut cube in cubes {
+        if expanded.iter().any(|e| covers(e, &cube)) {
+            continue;
+        }
+        for bit in (0..64).rev().map(|i| 1u64 << i) {
+            if cube.mask & bit == 0 {
+                continue;
+            }
+            let candidate = Cube::new(cube.mask & !bit, cube.value);
+            if !off.iter().any(|o| intersects(o, &candidate)) {
+                cube = candidate;
+            }
+        }
+        expanded.push(cube);
+    }
+    without_contained(expanded)
+}
+
+// Removes cubes that the other cubes and the don't-cares already cover,
+// smallest cubes first.
+fn irredundant(mut cubes: Vec<Cube>, dont_care: &[Cube]) -> Vec<Cube> {
+    cubes.sort_by_key(|cube| std::cmp::Reverse(cube.literal_count()));
+    let mut i = 0;
+    while i < cubes.len() {
+        let rest: Vec<Cube> = cubes
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, cube)| *cube)
+            .chain(dont_care.iter().copied())
+            .collect();
+        if is_tautology(&cofactor(&rest, &cubes[i])) {
+            cubes.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+    cubes
+}
+
+// Shrinks every cube to the smallest one that still covers the rows no other
+// cube covers, which gives the next expand room to move.
+fn reduce(mut cubes: Vec<Cube>, dont_care: &[Cube]) -> Vec<Cube> {
+    cubes.sort_by_key(|cube| cube.literal_count());
+    let mut i = 0;
+    while i < cubes.len() {
+        let rest: Vec<Cube> = cubes
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, cube)| *cube)
+            .chain(dont_care.iter().copied())
+            .collect();
+        let uncovered = complement(&cofactor(&rest, &cubes[i]));
+        if uncovered.is_empty() {
+            cubes.remove(i);
+            continue;
+        }
+        let cube = cubes[i];
+        let inner = supercube(&uncovered);
+        cubes[i] = Cube::new(cube.mask | inner.mask, cube.value | inner.value);
+        i += 1;
+    }
+    cubes
+}
+
+fn cost(cubes: &[Cube]) -> (usize, u32) {
+    (
+        cubes.len(),
+        cubes.iter().map(|cube| cube.literal_count()).sum(),
+    )
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Statistics {
+    pub cubes_before: usize,
+    pub cubes_after: usize,
+    pub literals_before: u32,
+    pub literals_after: u32,
+    pub iterations: usize,
+}
+
+impl fmt::Display for Statistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "cubes: {} -> {}, literals: {} -> {}, iterations: {}",
+            self.cubes_before,
+            self.cubes_after,
+            self.literals_before,
+            self.literals_after,
+            self.iterations
+        )
+    }
+}
+
+// Near-minimal sum of products for `on`, free to use the rows of
+// `dont_care`. Repeats REDUCE, EXPAND and IRREDUNDANT while the cost drops.
+pub fn espresso(on: &Cover, dont_care: &Cover) -> (Cover, Statistics) {
+    if on.operands() != dont_care.operands() {
+        panic!("On-set and don't-care set need the same operands");
+    }
+    let dc = dont_care.cubes();
+    let all: Vec<Cube> = on.cubes().iter().chain(dc).copied().collect();
+    let off = complement(&all);
+    let mut best = irredundant(expand(on.cubes().to_vec(), &off), dc);
+    let mut iterations = 1;
+    loop {
+        let next = irredundant(expand(reduce(best.clone(), dc), &off), dc);
+        iterations += 1;
+        if cost(&next) >= cost(&best) {
+            break;
+        }
+        best = next;
+    }
+    best.sort_by_key(|cube| {
+        (
+            cube.literal_count(),
+            std::cmp::Reverse(cube.value),
+            cube.mask,
+        )
+    });
+    let statistics = Statistics {
+        cubes_before: on.cubes().len(),
+        cubes_after: best.len(),
+        literals_before: on.literal_count(),
+        literals_after: cost(&best).1,
+        iterations,
+    };
+    (Cover::new(on.operands(), best), statistics)
+}
+
+pub fn cover_of(operator: &Operator) -> Cover {
+    let operands = operator.operands();
+    let n = operands.len();
+    let bit = |c: char| 1u64 << (n - 1 - operands.iter().position(|&o| o == c).unwrap());
+    let cubes = operator
+        .disjunctive_cubes()
+        .into_iter()
+        .map(|literals| {
+            literals.into_iter().fold(Cube::new(0, 0), |cube, (c, b)| {
+                Cube::new(cube.mask | bit(c), cube.value | if b { bit(c) } else { 0 })
+            })
+        })
+        .collect();
+    Cover::new(&operands, cubes)
+}
+
+pub fn minimize_formula(operator: &Operator) -> (Cover, Statistics) {
+    let on = cover_of(operator);
+    let dont_care = Cover::new(on.operands(), vec![]);
+    espresso(&on, &dont_care)
+}
+
+pub fn minimize_incomplete(function: &IncompleteFunction) -> (Cover, Statistics) {
+    let n = function.operands().len();
+    let minterms = |terms: Vec<u64>| {
+        Cover::new(
+            function.operands(),
+            terms.into_iter().map(|m| Cube::minterm(n, m)).collect(),
+        )
+    };
+    espresso(
+        &minterms(function.on_set()),
+        &minterms(function.dont_care_set()),
+    )
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct PlaError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for PlaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for PlaError {}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Pla {
+    pub on: Cover,
+    pub dont_care: Cover,
+}
+
+fn pla_error(line: usize, message: String) -> PlaError {
+    PlaError { line, message }
+}
+
+// Single output PLA tables: `.i`, optional `.o 1`, `.ilb`, `.p` and `.type`,
+// then rows of `0`, `1` and `-` inputs with a `1` or `-` output. Rows with a
+// `0` output belong to the off-set and are skipped.
+pub fn parse_pla(text: &str) -> Result<Pla, PlaError> {
+    let mut inputs: Option<usize> = None;
+    let mut operands: Option<(usize, Vec<char>)> = None;
+    let mut on = vec![];
+    let mut dont_care = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let line_number = i + 1;
+        let line = line.split('#').next().unwrap().trim();
+        if line.is_empty() {
+            continue;
+        }
+        let mut fields = line.split_whitespace();
+        let keyword = fields.next().unwrap();
+        match keyword {
+            ".i" => {
+                let n = fields
+                    .next()
+                    .and_then(|f| f.parse::<usize>().ok())
+                    .filter(|&n| n > 0 && n < 64)
+                    .ok_or_else(|| pla_error(line_number, "Invalid input count".to_string()))?;
+                inputs = Some(n);
+            }
+            ".o" => {
+                if fields.next() != Some("1") {
+                    return Err(pla_error(
+                        line_number,
+                        "Only single output tables are supported".to_string(),
+                    ));
+                }
+            }
+            ".ilb" => {
+                let names: Vec<char> = fields
+                    .map(|name| {
+                        let mut chars = name.chars();
+                        match (chars.next(), chars.next()) {
+                            (Some(c), None) if c.is_alphabetic() => {
+                                Ok(c.to_uppercase().next().unwrap())
+                            }
+                            _ => Err(pla_error(
+                                line_number,
+                                format!("Invalid input name '{}'", name),
+                            )),
+                        }
+                    })
+                    .collect::<Result<_, _>>()?;
+                if let Some(c) = names
+                    .iter()
+                    .find(|c| names.iter().filter(|d| d == c).count() > 1)
+                {
+                    return Err(pla_error(
+                        line_number,
+                        format!("Duplicate input name '{}'", c),
+                    ));
+                }
+                operands = Some((line_number, names));
+            }
+            ".p" | ".type" | ".ob" => {}
+            ".e" | ".end" => break,
+            _ if keyword.starts_with('.') => {
+                return Err(pla_error(
+                    line_number,
+                    format!("Unknown directive '{}'", keyword),
+                ))
+            }
+            _ => {
+                let n = inputs
+                    .ok_or_else(|| pla_error(line_number, "Cube before the .i line".to_string()))?;
+                let output = fields
+                    .next()
+                    .ok_or_else(|| pla_error(line_number, "Missing output column".to_string()))?;
+                if keyword.chars().count() != n {
+                    return Err(pla_error(
+                        line_number,
+                        format!("Expected {} inputs, found {}", n, keyword.chars().count()),
+                    ));
+                }
+                let mut cube = Cube::new(0, 0);
+                for (i, c) in keyword.chars().enumerate() {
+                    let bit = 1u64 << (n - 1 - i);
+                    cube = match c {
+                        '0' => Cube::new(cube.mask | bit, cube.value),
+                        '1' => Cube::new(cube.mask | bit, cube.value | bit),
+                        '-' | '2' => cube,
+                        _ => {
+                            return Err(pla_error(
+                                line_number,
+                                format!("Invalid input value '{}'", c),
+                            ))
+                        }
+                    };
+                }
+                match output {
+                    "1" | "4" => on.push(cube),
+                    "-" | "2" => dont_care.push(cube),
+                    "0" | "~" => {}
+                    _ => {
+                        return Err(pla_error(
+                            line_number,
+                            format!("Invalid output value '{}'", output),
+                        ))
+                    }
+                }
+            }
+        }
+    }
+    let n = inputs.ok_or_else(|| pla_error(1, "Missing .i line".to_string()))?;
+    let operands = match operands {
+        Some((_, names)) if names.len() == n => names,
+        Some((line_number, names)) => {
+            return Err(pla_error(
+                line_number,
+                format!("Expected {} input names, found {}", n, names.len()),
+            ))
+        }
+        None => default_operands(n)
+            .ok_or_else(|| pla_error(1, "Too many inputs without an .ilb line".to_string()))?,
+    };
+    Ok(Pla {
+        on: Cover::new(&operands, on),
+        dont_care: Cover::new(&operands, dont_care),
+    })
+}
+
+pub fn to_pla(cover: &Cover) -> String {
+    let n = cover.operands().len();
+    let mut text = format!(".i {}\n.o 1\n.ilb", n);
+    for c in cover.operands() {
+        text.push_str(&format!(" {}", c));
+    }
+    text.push_str(&format!("\n.p {}\n", cover.cubes().len()));
+    for cube in cover.cubes() {
+        let inputs: String = (0..n)
+            .map(|i| 1u64 << (n - 1 - i))
+            .map(|bit| match (cube.mask & bit != 0, cube.value & bit != 0) {
+                (false, _) => '-',
+                (true, false) => '0',
+                (true, true) => '1',
+            })
+            .collect();
+        text.push_str(&format!("{} 1\n", inputs));
+    }
+    text.push_str(".e\n");
+    text
+}
+
+pub fn minimize_pla(text: &str) -> Result<(Cover, Statistics), PlaError> {
+    let pla = parse_pla(text)?;
+    Ok(espresso(&pla.on, &pla.dont_care))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table_diff::equivalent;
+
+    fn tree(formula: &str) -> Operator {
+        Operator::from_formula(formula).unwrap()
+    }
+
+    #[test]
+    fn complement_and_tautology_agree() {
+        let cover = cover_of(&tree("AB&A!C&|")).cubes().to_vec();
+        let mut all = cover.clone();
+        all.extend(complement(&cover));
+
+        assert!(!is_tautology(&cover));
+        assert!(is_tautology(&all));
+        assert!(complement(&cover)
+            .iter()
+            .all(|c| !cover.iter().any(|d| intersects(c, d))));
+    }
+
+    #[test]
+    fn minimized_formulas_are_equivalent() {
+        for formula in [
+            "AB&AB!&|",
+            "AB&A!C&|BC&|",
+            "AB|C&A!B!&C!|&",
+            "AB^C^D^",
+            "ABCD&&&AB!C!D&&&|AB!CD&&&|",
+        ] {
+            let operator = tree(formula);
+            let (cover, _) = minimize_formula(&operator);
+
+            assert!(equivalent(&operator, &cover.to_operator()), "{}", formula);
+        }
+    }
+
+    #[test]
+    fn espresso_finds_the_consensus_free_form() {
+        let (cover, statistics) = minimize_formula(&tree("AB&A!C&|BC&|"));
+
+        assert_eq!(cover.to_operator().to_string(), "AB&A!C&|");
+        assert_eq!(statistics.cubes_before, 3);
+        assert_eq!(statistics.cubes_after, 2);
+        assert_eq!(statistics.literals_after, 4);
+    }
+
+    #[test]
+    fn dont_cares_are_used() {
+        let function =
+            IncompleteFunction::new(&['A', 'B', 'C', 'D'], &[4, 8, 10, 11, 12, 15], &[9, 14]);
+        let (cover, statistics) = minimize_incomplete(&function);
+
+        assert!(function.is_implemented_by(&cover.to_operator()));
+        assert_eq!(statistics.cubes_after, 3);
+        assert_eq!(statistics.literals_after, 7);
+    }
+
+    #[test]
+    fn thirty_input_pla_is_minimized() {
+        let mut text = String::from("# merges into one cube\n.i 30\n.o 1\n");
+        for k in 0..8 {
+            text.push_str(&format!("{:03b}1{} 1\n", k, "-".repeat(26)));
+        }
+        text.push_str(&format!(
+            "{}1 1\n{}0 -\n.e\n",
+            "0".repeat(29),
+            "1".repeat(29)
+        ));
+        let (cover, statistics) = minimize_pla(&text).unwrap();
+
+        assert_eq!(cover.operands().len(), 30);
+        assert_eq!(cover.cubes().len(), 2);
+        assert_eq!(cover.cubes()[0], Cube::new(1 << 26, 1 << 26));
+        assert_eq!(statistics.cubes_before, 9);
+        assert_eq!(statistics.literals_before, 62);
+        assert_eq!(statistics.literals_after, 1 + 29);
+    }
+
+    #[test]
+    fn default_names_are_fifty_letters() {
+        let names = default_operands(50).unwrap();
+
+        assert!(names.iter().all(|c| c.is_alphabetic()));
+        assert_eq!(names[25..28], ['Z', 'Α', 'Β']);
+        assert_eq!(names[42..44], ['Ρ', 'Σ']);
+        assert_eq!(names[49], 'Ω');
+        assert!(default_operands(51).is_none());
+    }
+
+    #[test]
+    fn pla_round_trips() {
+        let text = ".i 3\n.o 1\n.ilb x y z\n1-0 1\n011 1\n00- -\n.e\n";
+        let pla = parse_pla(text).unwrap();
+
+        assert_eq!(pla.on.operands(), &['X', 'Y', 'Z']);
+        assert_eq!(pla.dont_care.cubes(), &[Cube::new(0b110, 0b000)]);
+        assert_eq!(parse_pla(&to_pla(&pla.on)).unwrap().on, pla.on);
+    }
+
+    #[test]
+    fn pla_errors_have_line_numbers() {
+        let error = parse_pla(".i 2\n.o 1\n1- 1\n1 1\n").unwrap_err();
+        assert_eq!(error.line, 4);
+
+        let error = parse_pla(".i 2\n.o 2\n").unwrap_err();
+        assert_eq!(error.line, 2);
+
+        let error = parse_pla(".i 2\n1x 1\n").unwrap_err();
+        assert_eq!(error.message, "Invalid input value 'x'");
+
+        assert_eq!(parse_pla("11 1\n").unwrap_err().line, 1);
+
+        let error = parse_pla(".i 2\n.o 1\n.ilb a A\n11 1\n").unwrap_err();
+        assert_eq!(error.line, 3);
+        assert_eq!(error.message, "Duplicate input name 'A'");
+
+        let error = parse_pla("# names\n.i 3\n.o 1\n.ilb a b\n111 1\n").unwrap_err();
+        assert_eq!(error.line, 4);
+        assert_eq!(error.message, "Expected 3 input names, found 2");
+    }
+}
@@ -28,11 +28,7 @@ impl Operator {
     }
 
     pub fn is_operand(&self) -> bool {
-        match self {
-            Operator::True => true,
-            Operator::False => true,
-            _ => false,
-        }
+        matches!(self, Operator::True | Operator::False)
     }
 
     fn is_not(&self) -> bool {
@@ -0,0 +1,214 @@
+use itertools::Itertools;
+
+use crate::algebra::BitSet;
+use crate::cube::full_mask;
+use crate::operator::Operator;
+use crate::truth_table::TruthTable;
+
+pub const MAX_WORD_OPERANDS: usize = 6;
+
+// Bit `i` of the word is the result of truth table row `i`.
+pub fn table_word(table: &TruthTable) -> u64 {
+    if table.operands().len() > MAX_WORD_OPERANDS {
+        panic!(
+            "A truth table word holds at most {} operands",
+            MAX_WORD_OPERANDS
+        );
+    }
+    table.block(0)
+}
+
+pub fn table_bits(table: &TruthTable) -> BitSet {
+    let words = (0..table.blocks())
+        .map(|block| table.block(block))
+        .collect();
+    BitSet::from_words(table.len() as usize, words)
+}
+
+pub fn fingerprint(operator: &Operator) -> u64 {
+    table_word(&TruthTable::new(operator.clone(), &operator.operands()))
+}
+
+pub fn fingerprint_bits(operator: &Operator) -> BitSet {
+    table_bits(&TruthTable::new(operator.clone(), &operator.operands()))
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+pub struct NpnTransform {
+    // Bit `i` of a row index of the result comes from bit `permutation[i]` of
+    // the original row index.
+    pub permutation: Vec<usize>,
+    pub input_negation: u64,
+    pub output_negation: bool,
+}
+
+impl NpnTransform {
+    pub fn apply(&self, word: u64) -> u64 {
+        let n = self.permutation.len();
+        let rows = 1u64 << n;
+        let mut result = 0;
+        for row in 0..rows {
+            let mut source = 0;
+            for (i, &p) in self.permutation.iter().enumerate() {
+                source |= (row >> i & 1) << p;
+            }
+            source ^= self.input_negation;
+            result |= (word >> source & 1) << row;
+        }
+        if self.output_negation {
+            result = !result;
+        }
+        result & full_mask(rows as usize)
+    }
+}
+
+// Smallest word reachable by permuting, negating inputs and negating the
+// output, together with the transform that reaches it. Tries all
+// n! * 2^n * 2 transforms, which stays cheap up to six operands.
+pub fn npn_canonical(word: u64, n: usize) -> (u64, NpnTransform) {
+    if n > MAX_WORD_OPERANDS {
+        panic!(
+            "NPN canonical forms support at most {} operands",
+            MAX_WORD_OPERANDS
+        );
+    }
+    let rows = 1usize << n;
+    let word = word & full_mask(rows);
+    let mut best = (
+        word,
+        NpnTransform {
+            permutation: (0..n).collect(),
+            input_negation: 0,
+            output_negation: false,
+        },
+    );
+    for permutation in (0..n).permutations(n) {
+        let sources: Vec<u64> = (0..rows as u64)
+            .map(|row| {
+                permutation
+                    .iter()
+                    .enumerate()
+                    .fold(0, |acc, (i, &p)| acc | (row >> i & 1) << p)
+            })
+            .collect();
+        for input_negation in 0..rows as u64 {
+            let mut result = 0u64;
+            for (row, source) in sources.iter().enumerate() {
+                result |= (word >> (source ^ input_negation) & 1) << row;
+            }
+            for output_negation in [false, true] {
+                let candidate = if output_negation {
+                    !result & full_mask(rows)
+                } else {
+                    result
+                };
+                if candidate < best.0 {
+                    best = (
+                        candidate,
+                        NpnTransform {
+                            permutation: permutation.clone(),
+                            input_negation,
+                            output_negation,
+                        },
+                    );
+                }
+            }
+        }
+    }
+    best
+}
+
+fn padded_table(operator: &Operator, n: usize) -> TruthTable {
+    let mut operands = operator.operands();
+    let mut spare = ('A'..='Z')
+        .filter(|c| !operands.contains(c))
+        .collect::<Vec<char>>();
+    spare.reverse();
+    while operands.len() < n {
+        operands.push(spare.pop().expect("No spare operand names left"));
+    }
+    TruthTable::new(operator.clone(), &operands)
+}
+
+// Equal up to renaming, negating operands and negating the result. Formulas
+// with fewer operands are padded with operands they do not depend on.
+pub fn npn_equivalent(a: &Operator, b: &Operator) -> bool {
+    let n = a.operands().len().max(b.operands().len());
+    let a = table_word(&padded_table(a, n));
+    let b = table_word(&padded_table(b, n));
+    npn_canonical(a, n).0 == npn_canonical(b, n).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tree(formula: &str) -> Operator {
+        Operator::from_formula(formula).unwrap()
+    }
+
+    #[test]
+    fn fingerprint_holds_a_bit_per_row() {
+        assert_eq!(fingerprint(&tree("AB&")), 0b1000);
+        assert_eq!(fingerprint(&tree("AB>")), 0b1011);
+        assert_eq!(fingerprint(&tree("ABC||")), 0b1111_1110);
+    }
+
+    #[test]
+    fn fingerprint_bits_match_the_word() {
+        let operator = tree("ABC^^");
+        let bits = fingerprint_bits(&operator);
+
+        assert_eq!(bits.len(), 8);
+        assert_eq!(bits.words(), &[fingerprint(&operator)]);
+    }
+
+    #[test]
+    fn fingerprint_bits_work_for_many_operands() {
+        let bits = fingerprint_bits(&tree("ABCDEFGH&&&&&&&"));
+
+        assert_eq!(bits.len(), 256);
+        assert_eq!(bits.indices(), vec![255]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn fingerprint_of_too_many_operands_panics() {
+        fingerprint(&tree("ABCDEFG&&&&&&"));
+    }
+
+    #[test]
+    fn transform_reaches_the_canonical_word() {
+        for formula in ["AB>", "ABC|&", "AB^C>D="] {
+            let operator = tree(formula);
+            let n = operator.operands().len();
+            let word = fingerprint(&operator);
+            let (canonical, transform) = npn_canonical(word, n);
+
+            assert_eq!(transform.apply(word), canonical);
+        }
+    }
+
+    #[test]
+    fn npn_equivalent_functions_share_a_canonical_form() {
+        assert!(npn_equivalent(&tree("AB&"), &tree("AB|")));
+        assert!(npn_equivalent(&tree("AB>"), &tree("CD!&")));
+        assert!(npn_equivalent(&tree("AB^"), &tree("BA=")));
+        assert!(npn_equivalent(&tree("ABC|&"), &tree("C!AB!&|")));
+        assert!(!npn_equivalent(&tree("AB&"), &tree("AB^")));
+    }
+
+    #[test]
+    fn unused_operands_are_padded() {
+        assert!(npn_equivalent(&tree("A"), &tree("BC!C&|")));
+        assert!(!npn_equivalent(&tree("A"), &tree("BC&")));
+    }
+
+    #[test]
+    fn canonical_form_of_six_operands_works() {
+        let word = fingerprint(&tree("ABCDEF&&&&&"));
+        let (canonical, _) = npn_canonical(word, 6);
+
+        assert_eq!(canonical, 1);
+    }
+}
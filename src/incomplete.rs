@@ -0,0 +1,284 @@
+use std::fmt;
+use std::io;
+
+use crate::algebra::{BitSet, BooleanAlgebra};
+use crate::fingerprint::table_bits;
+use crate::operator::Operator;
+use crate::table_writer::{write_incomplete, TableFormat};
+use crate::truth_table::TruthTable;
+
+pub const MAX_OPERANDS: usize = 24;
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum Value {
+    False,
+    True,
+    DontCare,
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Value {
+        if b {
+            Value::True
+        } else {
+            Value::False
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::False => write!(f, "0"),
+            Value::True => write!(f, "1"),
+            Value::DontCare => write!(f, "-"),
+        }
+    }
+}
+
+// A function that is only specified on some rows. Row indices use the same
+// layout as truth tables, the on-set and the don't-care set never overlap.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct IncompleteFunction {
+    operands: Vec<char>,
+    on: BitSet,
+    dont_care: BitSet,
+}
+
+impl IncompleteFunction {
+    pub fn new(operands: &[char], on: &[u64], dont_care: &[u64]) -> IncompleteFunction {
+        if operands.len() > MAX_OPERANDS {
+            panic!(
+                "Incomplete functions support at most {} operands",
+                MAX_OPERANDS
+            );
+        }
+        let len = 1usize << operands.len();
+        let indices = |terms: &[u64]| {
+            terms
+                .iter()
+                .map(|&term| {
+                    if term >= len as u64 {
+                        panic!("Term {} out of range for {} operands", term, operands.len());
+                    }
+                    term as usize
+                })
+                .collect::<Vec<usize>>()
+        };
+        let on = BitSet::from_indices(len, &indices(on));
+        let dont_care = BitSet::from_indices(len, &indices(dont_care));
+        if on.meet(&dont_care).count_ones() > 0 {
+            panic!("A row cannot be both true and a don't-care");
+        }
+        IncompleteFunction {
+            operands: operands.to_vec(),
+            on,
+            dont_care,
+        }
+    }
+
+    // Rows where `dont_care` holds are left open, the others follow `on`.
+    pub fn from_formulas(on: &Operator, dont_care: &Operator) -> IncompleteFunction {
+        let mut operands = on.operands();
+        for c in dont_care.operands() {
+            if !operands.contains(&c) {
+                operands.push(c);
+            }
+        }
+        if operands.len() > MAX_OPERANDS {
+            panic!(
+                "Incomplete functions support at most {} operands",
+                MAX_OPERANDS
+            );
+        }
+        let dont_care = table_bits(&TruthTable::new(dont_care.clone(), &operands));
+        let on = table_bits(&TruthTable::new(on.clone(), &operands)).meet(&dont_care.complement());
+        IncompleteFunction {
+            operands,
+            on,
+            dont_care,
+        }
+    }
+
+    // One character per row in index order: '0', '1' or '-'.
+    pub fn from_table(operands: &[char], table: &str) -> IncompleteFunction {
+        let mut on = vec![];
+        let mut dont_care = vec![];
+        let mut rows = 0;
+        for (i, c) in table.chars().filter(|c| !c.is_whitespace()).enumerate() {
+            match c {
+                '0' => {}
+                '1' => on.push(i as u64),
+                '-' => dont_care.push(i as u64),
+                _ => panic!("Expected 0, 1 or -, found '{}'", c),
+            }
+            rows += 1;
+        }
+        if rows as u64 != 1u64 << operands.len() {
+            panic!(
+                "Table for {} operands needs {} values",
+                operands.len(),
+                1u64 << operands.len()
+            );
+        }
+        IncompleteFunction::new(operands, &on, &dont_care)
+    }
+
+    pub fn operands(&self) -> &[char] {
+        &self.operands
+    }
+
+    pub fn len(&self) -> u64 {
+        self.on.len() as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn value(&self, index: u64) -> Value {
+        if self.dont_care.contains(index as usize) {
+            Value::DontCare
+        } else {
+            Value::from(self.on.contains(index as usize))
+        }
+    }
+
+    pub fn values(&self, index: u64) -> Vec<(char, bool)> {
+        let n = self.operands.len();
+        self.operands
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| (c, index >> (n - 1 - i) & 1 == 1))
+            .collect()
+    }
+
+    pub fn on_set(&self) -> Vec<u64> {
+        self.on.indices().into_iter().map(|i| i as u64).collect()
+    }
+
+    pub fn off_set(&self) -> Vec<u64> {
+        self.on
+            .join(&self.dont_care)
+            .complement()
+            .indices()
+            .into_iter()
+            .map(|i| i as u64)
+            .collect()
+    }
+
+    pub fn dont_care_set(&self) -> Vec<u64> {
+        self.dont_care
+            .indices()
+            .into_iter()
+            .map(|i| i as u64)
+            .collect()
+    }
+
+    pub fn on_bits(&self) -> &BitSet {
+        &self.on
+    }
+
+    pub fn dont_care_bits(&self) -> &BitSet {
+        &self.dont_care
+    }
+
+    // True when the formula agrees with every specified row. Operands of the
+    // formula that the function does not know about make it fail.
+    pub fn is_implemented_by(&self, operator: &Operator) -> bool {
+        if operator
+            .operands()
+            .iter()
+            .any(|c| !self.operands.contains(c))
+        {
+            return false;
+        }
+        let bits = table_bits(&TruthTable::new(operator.clone(), &self.operands));
+        let care = self.dont_care.complement();
+        bits.meet(&care) == self.on
+    }
+}
+
+pub fn print_incomplete(function: &IncompleteFunction) {
+    print_incomplete_as(function, TableFormat::Pipe);
+}
+
+pub fn print_incomplete_as(function: &IncompleteFunction, format: TableFormat) {
+    write_incomplete(function, format.writer().as_mut(), &mut io::stdout().lock())
+        .expect("Could not write truth table");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table_writer::PipeWriter;
+
+    fn tree(formula: &str) -> Operator {
+        Operator::from_formula(formula).unwrap()
+    }
+
+    #[test]
+    fn sets_partition_the_rows() {
+        let function = IncompleteFunction::new(&['A', 'B', 'C'], &[1, 3, 7], &[0, 5]);
+
+        assert_eq!(function.on_set(), vec![1, 3, 7]);
+        assert_eq!(function.dont_care_set(), vec![0, 5]);
+        assert_eq!(function.off_set(), vec![2, 4, 6]);
+        assert_eq!(function.len(), 8);
+        assert!(!function.is_empty());
+        assert_eq!(function.value(0), Value::DontCare);
+        assert_eq!(function.value(2), Value::False);
+        assert_eq!(function.value(7), Value::True);
+    }
+
+    #[test]
+    fn table_string_works() {
+        let function = IncompleteFunction::from_table(&['A', 'B'], "01-1");
+
+        assert_eq!(function.on_set(), vec![1, 3]);
+        assert_eq!(function.dont_care_set(), vec![2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn overlapping_sets_panic() {
+        IncompleteFunction::new(&['A', 'B'], &[1, 2], &[2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn table_string_with_wrong_length_panics() {
+        IncompleteFunction::from_table(&['A', 'B'], "01-");
+    }
+
+    #[test]
+    fn don_t_care_formula_wins_over_on_formula() {
+        let function = IncompleteFunction::from_formulas(&tree("AB|"), &tree("AB&"));
+
+        assert_eq!(function.operands(), &['A', 'B']);
+        assert_eq!(function.on_set(), vec![1, 2]);
+        assert_eq!(function.dont_care_set(), vec![3]);
+    }
+
+    #[test]
+    fn implementations_may_choose_don_t_care_rows() {
+        let function = IncompleteFunction::from_table(&['A', 'B'], "011-");
+
+        assert!(function.is_implemented_by(&tree("AB|")));
+        assert!(function.is_implemented_by(&tree("AB^")));
+        assert!(!function.is_implemented_by(&tree("AB&")));
+        assert!(!function.is_implemented_by(&tree("AC|")));
+    }
+
+    #[test]
+    fn don_t_cares_are_written_as_dashes() {
+        let function = IncompleteFunction::from_table(&['A'], "-1");
+        let mut out = vec![];
+        write_incomplete(&function, &mut PipeWriter, &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "| A | = |\n|---|---|\n| 0 | - |\n| 1 | 1 |\n"
+        );
+    }
+}
@@ -0,0 +1,384 @@
+use crate::canonical::sum_of_products;
+use crate::cube::Cube;
+use crate::gray_code::gray_code;
+use crate::incomplete::{IncompleteFunction, Value};
+use crate::operator::Operator;
+use crate::quine_mccluskey::{minimum_cover, prime_implicants};
+use crate::truth_table::TruthTable;
+
+const CELL_SIZE: usize = 40;
+const MARGIN: usize = 60;
+const GROUP_COLORS: [&str; 6] = [
+    "#e6194b", "#3cb44b", "#4363d8", "#f58231", "#911eb4", "#42d4f4",
+];
+
+fn bits_label(value: u32, width: usize) -> String {
+    format!("{:0width$b}", value, width = width)
+}
+
+fn group_name(i: usize) -> char {
+    (b'a' + (i % 26) as u8) as char
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct KarnaughMap {
+    table: TruthTable,
+    row_bits: usize,
+    col_bits: usize,
+    groups: Vec<Cube>,
+    dont_care: Vec<u64>,
+}
+
+impl KarnaughMap {
+    pub fn new(operator: Operator, operands: &[char]) -> KarnaughMap {
+        if !(2..=6).contains(&operands.len()) {
+            panic!("Karnaugh maps need between 2 and 6 operands");
+        }
+        KarnaughMap {
+            table: TruthTable::new(operator, operands),
+            row_bits: operands.len() / 2,
+            col_bits: operands.len() - operands.len() / 2,
+            groups: vec![],
+            dont_care: vec![],
+        }
+    }
+
+    // None for formulas with fewer than 2 or more than 6 operands.
+    pub fn from_formula(formula: &str) -> Option<KarnaughMap> {
+        let operator = Operator::from_formula(formula)?;
+        let operands = operator.operands();
+        if !(2..=6).contains(&operands.len()) {
+            return None;
+        }
+        Some(KarnaughMap::new(operator, &operands))
+    }
+
+    pub fn from_incomplete(function: &IncompleteFunction) -> KarnaughMap {
+        let operator = sum_of_products(function.operands(), &function.on_set());
+        KarnaughMap::new(operator, function.operands()).with_dont_cares(function.dont_care_set())
+    }
+
+    pub fn with_dont_cares(mut self, dont_care: Vec<u64>) -> KarnaughMap {
+        self.dont_care = dont_care;
+        self
+    }
+
+    pub fn with_groups(mut self, groups: Vec<Cube>) -> KarnaughMap {
+        self.groups = groups;
+        self
+    }
+
+    // Groups of a minimum sum of products, don't-care cells may be grouped.
+    pub fn with_minimal_groups(self) -> KarnaughMap {
+        let on: Vec<u64> = self
+            .table
+            .minterms()
+            .into_iter()
+            .filter(|m| !self.dont_care.contains(m))
+            .collect();
+        let primes = prime_implicants(self.operands().len(), &on, &self.dont_care);
+        let groups = minimum_cover(&primes, &on);
+        self.with_groups(groups)
+    }
+
+    pub fn groups(&self) -> &[Cube] {
+        &self.groups
+    }
+
+    pub fn operands(&self) -> &[char] {
+        self.table.operands()
+    }
+
+    pub fn height(&self) -> usize {
+        1 << self.row_bits
+    }
+
+    pub fn width(&self) -> usize {
+        1 << self.col_bits
+    }
+
+    pub fn row_labels(&self) -> Vec<String> {
+        (0..self.height() as u32)
+            .map(|r| bits_label(gray_code(r), self.row_bits))
+            .collect()
+    }
+
+    pub fn col_labels(&self) -> Vec<String> {
+        (0..self.width() as u32)
+            .map(|c| bits_label(gray_code(c), self.col_bits))
+            .collect()
+    }
+
+    pub fn index(&self, row: usize, col: usize) -> u64 {
+        ((gray_code(row as u32) as u64) << self.col_bits) | gray_code(col as u32) as u64
+    }
+
+    pub fn cell(&self, row: usize, col: usize) -> bool {
+        self.table.row(self.index(row, col)).result
+    }
+
+    pub fn value(&self, row: usize, col: usize) -> Value {
+        if self.dont_care.contains(&self.index(row, col)) {
+            Value::DontCare
+        } else {
+            Value::from(self.cell(row, col))
+        }
+    }
+
+    fn corner_label(&self) -> String {
+        let (rows, cols) = self.operands().split_at(self.row_bits);
+        format!(
+            "{}\\{}",
+            rows.iter().collect::<String>(),
+            cols.iter().collect::<String>()
+        )
+    }
+
+    fn groups_at(&self, row: usize, col: usize) -> String {
+        let index = self.index(row, col);
+        self.groups
+            .iter()
+            .enumerate()
+            .filter(|(_, group)| group.contains(index))
+            .map(|(i, _)| group_name(i))
+            .collect()
+    }
+
+    pub fn to_text(&self) -> String {
+        let corner = self.corner_label();
+        let cell_width = self.col_bits.max(1 + self.groups.len());
+        let mut text = format!("| {} ", corner);
+        for label in self.col_labels() {
+            text.push_str(&format!("| {:cell_width$} ", label));
+        }
+        text.push_str("|\n");
+        text.push_str(&format!("|{}", "-".repeat(corner.len() + 2)));
+        for _ in 0..self.width() {
+            text.push_str(&format!("|{}", "-".repeat(cell_width + 2)));
+        }
+        text.push_str("|\n");
+        for (row, label) in self.row_labels().iter().enumerate() {
+            text.push_str(&format!("| {:width$} ", label, width = corner.len()));
+            for col in 0..self.width() {
+                let cell = format!("{}{}", self.value(row, col), self.groups_at(row, col));
+                text.push_str(&format!("| {:cell_width$} ", cell));
+            }
+            text.push_str("|\n");
+        }
+        for (i, group) in self.groups.iter().enumerate() {
+            let literals: Vec<String> = group
+                .literals(self.operands())
+                .iter()
+                .map(|(c, b)| if *b { c.to_string() } else { format!("!{}", c) })
+                .collect();
+            text.push_str(&format!("{}: {}\n", group_name(i), literals.join(" & ")));
+        }
+        text
+    }
+
+    fn group_outline(&self, i: usize, group: &Cube) -> String {
+        let inset = 3 + 3 * i;
+        let color = GROUP_COLORS[i % GROUP_COLORS.len()];
+        let in_group = |row: usize, col: usize| group.contains(self.index(row, col));
+        let mut svg = String::new();
+        let mut line = |x1: usize, y1: usize, x2: usize, y2: usize| {
+            svg.push_str(&format!(
+                "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"2\"/>\n",
+                x1, y1, x2, y2, color
+            ));
+        };
+        for row in 0..self.height() {
+            for col in 0..self.width() {
+                if !in_group(row, col) {
+                    continue;
+                }
+                let left = MARGIN + col * CELL_SIZE + inset;
+                let right = MARGIN + (col + 1) * CELL_SIZE - inset;
+                let top = MARGIN + row * CELL_SIZE + inset;
+                let bottom = MARGIN + (row + 1) * CELL_SIZE - inset;
+                if row == 0 || !in_group(row - 1, col) {
+                    line(left, top, right, top);
+                }
+                if row + 1 == self.height() || !in_group(row + 1, col) {
+                    line(left, bottom, right, bottom);
+                }
+                if col == 0 || !in_group(row, col - 1) {
+                    line(left, top, left, bottom);
+                }
+                if col + 1 == self.width() || !in_group(row, col + 1) {
+                    line(right, top, right, bottom);
+                }
+            }
+        }
+        svg
+    }
+
+    pub fn to_svg(&self) -> String {
+        let width = MARGIN + self.width() * CELL_SIZE + 10;
+        let height = MARGIN + self.height() * CELL_SIZE + 10;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"monospace\" font-size=\"14\">\n",
+            width, height
+        );
+        svg.push_str(&format!(
+            "  <text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n",
+            MARGIN - 5,
+            MARGIN - 25,
+            self.corner_label()
+        ));
+        for (col, label) in self.col_labels().iter().enumerate() {
+            svg.push_str(&format!(
+                "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+                MARGIN + col * CELL_SIZE + CELL_SIZE / 2,
+                MARGIN - 8,
+                label
+            ));
+        }
+        for (row, label) in self.row_labels().iter().enumerate() {
+            svg.push_str(&format!(
+                "  <text x=\"{}\" y=\"{}\" text-anchor=\"end\">{}</text>\n",
+                MARGIN - 8,
+                MARGIN + row * CELL_SIZE + CELL_SIZE / 2 + 5,
+                label
+            ));
+        }
+        for row in 0..self.height() {
+            for col in 0..self.width() {
+                let x = MARGIN + col * CELL_SIZE;
+                let y = MARGIN + row * CELL_SIZE;
+                svg.push_str(&format!(
+                    "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"white\" stroke=\"black\"/>\n",
+                    x, y, CELL_SIZE, CELL_SIZE
+                ));
+                svg.push_str(&format!(
+                    "  <text x=\"{}\" y=\"{}\" text-anchor=\"middle\">{}</text>\n",
+                    x + CELL_SIZE / 2,
+                    y + CELL_SIZE / 2 + 5,
+                    self.value(row, col)
+                ));
+            }
+        }
+        for (i, group) in self.groups.iter().enumerate() {
+            svg.push_str(&self.group_outline(i, group));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+pub fn print_karnaugh_map(formula: &str) {
+    if let Some(map) = KarnaughMap::from_formula(formula) {
+        print!("{}", map.to_text());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_follow_gray_code() {
+        let map = KarnaughMap::from_formula("ABC&&").unwrap();
+
+        assert_eq!(map.row_labels(), vec!["0", "1"]);
+        assert_eq!(map.col_labels(), vec!["00", "01", "11", "10"]);
+    }
+
+    #[test]
+    fn cells_follow_gray_code() {
+        let map = KarnaughMap::from_formula("AB^").unwrap();
+
+        assert!(!map.cell(0, 0));
+        assert!(map.cell(0, 1));
+        assert!(map.cell(1, 0));
+        assert!(!map.cell(1, 1));
+    }
+
+    #[test]
+    fn text_rendering_works() {
+        let map = KarnaughMap::from_formula("AB|C&").unwrap();
+
+        assert_eq!(
+            map.to_text(),
+            "| A\\BC | 00 | 01 | 11 | 10 |\n\
+             |------|----|----|----|----|\n\
+             | 0    | 0  | 0  | 1  | 0  |\n\
+             | 1    | 0  | 1  | 1  | 0  |\n"
+        );
+    }
+
+    #[test]
+    fn text_rendering_shows_groups() {
+        let map = KarnaughMap::from_formula("AB|C&")
+            .unwrap()
+            .with_groups(vec![Cube::new(0b101, 0b101), Cube::new(0b011, 0b011)]);
+        let text = map.to_text();
+
+        assert!(text.contains("| 1    | 0   | 1a  | 1ab | 0   |"));
+        assert!(text.ends_with("a: A & C\nb: B & C\n"));
+    }
+
+    #[test]
+    fn svg_rendering_contains_every_cell() {
+        let map = KarnaughMap::from_formula("ABCD&&&")
+            .unwrap()
+            .with_groups(vec![Cube::new(0b1111, 0b1111)]);
+        let svg = map.to_svg();
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<rect").count(), 16);
+        assert_eq!(svg.matches("<line").count(), 4);
+    }
+
+    #[test]
+    fn groups_wrapping_around_the_edge_are_outlined_per_cell() {
+        let map = KarnaughMap::from_formula("ABC&&")
+            .unwrap()
+            .with_groups(vec![Cube::new(0b101, 0b000)]);
+
+        assert_eq!(map.to_svg().matches("<line").count(), 8);
+        let map = map.with_groups(vec![Cube::new(0b110, 0b000)]);
+        assert_eq!(map.to_svg().matches("<line").count(), 6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn too_many_operands_panics() {
+        let operator = Operator::from_formula("ABCDEFG&&&&&&").unwrap();
+        KarnaughMap::new(operator.clone(), &operator.operands());
+    }
+
+    #[test]
+    fn formulas_outside_the_map_sizes_give_none() {
+        for formula in ["A", "1", "A0|", "ABCDEFG&&&&&&"] {
+            assert!(KarnaughMap::from_formula(formula).is_none(), "{}", formula);
+        }
+        print_karnaugh_map("A");
+        print_karnaugh_map("1");
+    }
+
+    #[test]
+    fn don_t_cares_are_shown_as_dashes() {
+        let function = IncompleteFunction::from_table(&['A', 'B'], "01-1");
+        let map = KarnaughMap::from_incomplete(&function);
+
+        assert_eq!(map.value(1, 0), Value::DontCare);
+        assert_eq!(map.value(1, 1), Value::True);
+        assert!(map.to_text().contains("| 1   | - | 1 |"));
+    }
+
+    #[test]
+    fn minimal_groups_come_from_the_minimizer() {
+        let map = KarnaughMap::from_formula("AB&A!C&|BC&|")
+            .unwrap()
+            .with_minimal_groups();
+
+        assert_eq!(map.groups().len(), 2);
+        assert!(map.to_text().contains("a: A & B\n"));
+
+        let function = IncompleteFunction::from_table(&['A', 'B'], "01-0");
+        let map = KarnaughMap::from_incomplete(&function).with_minimal_groups();
+        assert_eq!(map.groups(), &[Cube::new(0b11, 0b01)]);
+    }
+}
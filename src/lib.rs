@@ -0,0 +1,36 @@
+pub mod ac_canonical;
+pub mod adder;
+pub mod algebra;
+pub mod algebraic_normal_form;
+pub mod basis;
+pub mod canonical;
+pub mod cnf;
+pub mod cnf_encoding;
+pub mod column_table;
+pub mod conjunctive_normal_form;
+pub mod cube;
+pub mod curve;
+pub mod derivation;
+pub mod dimacs;
+pub mod disjunctive_normal_form;
+pub mod espresso;
+pub mod evaluation;
+pub mod fingerprint;
+pub mod gray_code;
+pub mod incomplete;
+pub mod inverse_curve;
+pub mod karnaugh;
+pub mod multiplier;
+pub mod negation_normal_form;
+pub mod operator;
+pub mod parallel;
+pub mod powerset;
+pub mod prime_implicates;
+pub mod probability;
+pub mod quine_mccluskey;
+pub mod sat;
+pub mod set_evaluation;
+pub mod simplify;
+pub mod table_diff;
+pub mod table_writer;
+pub mod truth_table;
@@ -1,20 +1,54 @@
-use gray_code::gray_code;
-
-mod adder;
-mod conjunctive_normal_form;
-mod curve;
-mod evaluation;
-mod gray_code;
-mod inverse_curve;
-mod multiplier;
-mod negation_normal_form;
-mod operator;
-mod powerset;
-mod sat;
-mod set_evaluation;
-mod truth_table;
+use ft_ready_set_boole::basis::{self, Basis};
+use ft_ready_set_boole::cnf_encoding::CnfEncoding;
+use ft_ready_set_boole::column_table::ColumnLabel;
+use ft_ready_set_boole::derivation::{self, Notation};
+use ft_ready_set_boole::gray_code::gray_code;
+use ft_ready_set_boole::incomplete::{self, IncompleteFunction};
+use ft_ready_set_boole::operator::Operator;
+use ft_ready_set_boole::table_writer::TableFormat;
+use ft_ready_set_boole::truth_table::TruthTable;
+use ft_ready_set_boole::{
+    adder, algebraic_normal_form, column_table, conjunctive_normal_form, curve, dimacs,
+    disjunctive_normal_form, espresso, evaluation, inverse_curve, karnaugh, multiplier,
+    negation_normal_form, powerset, prime_implicates, probability, quine_mccluskey, sat,
+    set_evaluation, simplify, table_diff, truth_table,
+};
+
+fn print_usage() {
+    eprintln!("usage: ft_ready_set_boole [--format pipe|csv|json|markdown|latex|html] <formula>");
+}
+
+fn print_formula_table(args: &[String]) -> Result<(), String> {
+    let mut format = TableFormat::Pipe;
+    let mut formula = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--format" | "-f" => {
+                let value = args.next().ok_or("Missing value for --format")?;
+                format = value.parse()?;
+            }
+            _ if formula.is_none() => formula = Some(arg),
+            _ => return Err(format!("Unexpected argument '{}'", arg)),
+        }
+    }
+    let formula = formula.ok_or("Missing formula")?;
+    let table = TruthTable::from_formula(formula).ok_or("Empty formula")?;
+    truth_table::print_table_as(&table, format);
+    Ok(())
+}
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        if let Err(error) = print_formula_table(&args) {
+            eprintln!("{}", error);
+            print_usage();
+            std::process::exit(1);
+        }
+        return;
+    }
+
     let something = 10;
     let something_else = 10;
 
@@ -36,6 +70,8 @@ fn main() {
     );
 
     truth_table::print_truth_table("ABC|&");
+    karnaugh::print_karnaugh_map("ABC|&");
+    column_table::print_truth_table_with_subformulas("ABC|&", ColumnLabel::Infix);
 
     let expression = "AB&!";
     let result = negation_normal_form::negation_normal_form(expression);
@@ -51,10 +87,78 @@ fn main() {
         expression, result
     );
 
+    let expression = "AB>!";
+    println!(
+        "The negation normal form of the expression '{}' is derived as:\n{}",
+        expression,
+        derivation::explain_negation_normal_form(expression, Notation::Infix)
+    );
+
+    let expression = "AB|C&";
+    let result = disjunctive_normal_form::disjunctive_normal_form(expression);
+    println!(
+        "The disjunctive normal form of the expression '{}' is: {}",
+        expression, result
+    );
+
+    let expression = "AB|";
+    let result = algebraic_normal_form::algebraic_normal_form(expression);
+    println!(
+        "The algebraic normal form of the expression '{}' is: {}",
+        expression, result
+    );
+
+    let expression = "AB|C|AB|&A!!&";
+    let result = simplify::simplify(expression);
+    println!(
+        "The simplified form of the expression '{}' is: {}",
+        expression, result
+    );
+
+    let expression = "AB&A!C&|BC&|";
+    let result = quine_mccluskey::minimal_sum_of_products(expression);
+    println!(
+        "The minimal sum of products of the expression '{}' is: {}",
+        expression, result
+    );
+
+    let operator = Operator::from_formula("AB&A!C&|BC&|AB!&C&|").unwrap();
+    let (cover, statistics) = espresso::minimize_formula(&operator);
+    println!(
+        "Espresso turns '{}' into '{}' ({})",
+        operator,
+        cover.to_operator(),
+        statistics
+    );
+
+    let operator = Operator::from_formula("AB&A!C&|").unwrap();
+    let blake = prime_implicates::blake_canonical_form(&operator);
+    println!(
+        "The Blake canonical form of '{}' is: {}",
+        operator,
+        blake.to_operator()
+    );
+
+    let operator = Operator::from_formula("AB^").unwrap();
+    let nand = basis::to_basis(&operator, Basis::Nand).unwrap();
+    println!(
+        "With Nand gates only '{}' becomes '{}' ({})",
+        operator,
+        nand,
+        basis::size_report(&nand)
+    );
+
     let expression = "AB&!";
     let result = sat::sat(expression);
     println!("The expression '{}' is satisfiable: {}", expression, result);
 
+    let operator = Operator::from_formula("AB|C>").unwrap();
+    print!(
+        "In DIMACS '{}' is:\n{}",
+        operator,
+        dimacs::to_dimacs(&operator, CnfEncoding::Tseitin)
+    );
+
     let set = vec![1, 2, 3];
     let result = powerset::powerset(set.clone());
     println!("The powerset of the set {:?} is: {:?}", set, result);
@@ -80,4 +184,16 @@ fn main() {
         "The result from reversing the mapping of {} is: {:?}",
         result, inverse
     );
+
+    let expression = "AB&C|";
+    let operator = Operator::from_formula(expression).unwrap();
+    let result = probability::probability(&operator, |_| 0.5);
+    println!(
+        "The probability of '{}' with fair operands is: {}",
+        expression, result
+    );
+
+    table_diff::print_truth_table_diff("AB>", "BA>");
+
+    incomplete::print_incomplete(&IncompleteFunction::from_table(&['A', 'B'], "01-1"));
 }
@@ -1,81 +1,63 @@
+use crate::derivation::{map_children, Law, Trace};
 use crate::operator::Operator;
 
-impl Operator {
-    pub fn to_negation_normal_form(self) -> Operator {
-        match self {
-            Operator::Not(c) => match *c {
-                Operator::Operand(_) => Operator::not(c.to_negation_normal_form()),
-                Operator::Not(d) => d.to_negation_normal_form(),
-                Operator::And(a, b) => Operator::with_two(
-                    '|',
-                    Operator::not(*a).to_negation_normal_form(),
-                    Operator::not(*b).to_negation_normal_form(),
-                ),
-                Operator::Or(a, b) => Operator::with_two(
-                    '&',
-                    Operator::not(*a).to_negation_normal_form(),
-                    Operator::not(*b).to_negation_normal_form(),
-                ),
-                Operator::Implies(a, b) => Operator::with_two(
-                    '|',
-                    a.to_negation_normal_form(),
-                    Operator::not(b.to_negation_normal_form()),
-                ),
-                Operator::Xor(a, b) => Operator::with_two(
-                    '=',
-                    a.to_negation_normal_form(),
-                    b.to_negation_normal_form(),
-                )
-                .to_negation_normal_form(),
-                Operator::Equals(a, b) => Operator::with_two(
-                    '^',
-                    a.to_negation_normal_form(),
-                    b.to_negation_normal_form(),
-                )
-                .to_negation_normal_form(),
-            },
-            Operator::Equals(a, b) => Operator::with_two(
-                '|',
-                Operator::with_two(
-                    '&',
-                    a.clone().to_negation_normal_form(),
-                    b.clone().to_negation_normal_form(),
-                ),
-                Operator::with_two(
-                    '&',
-                    Operator::not(a.to_negation_normal_form()),
-                    Operator::not(b.to_negation_normal_form()),
-                ),
-            ),
-            Operator::Xor(a, b) => Operator::with_two(
-                '|',
-                Operator::with_two(
-                    '&',
-                    Operator::not(a.clone().to_negation_normal_form()),
-                    b.clone().to_negation_normal_form(),
-                ),
-                Operator::with_two(
-                    '&',
-                    a.to_negation_normal_form(),
-                    Operator::not(b.to_negation_normal_form()),
-                ),
-            ),
-            Operator::Implies(a, b) => Operator::with_two(
-                '|',
-                Operator::not(a.to_negation_normal_form()),
-                b.to_negation_normal_form(),
+// One rewrite at the root of `node`, or `node` back when it is an And, an Or
+// or a literal. `Derivation` replays recorded steps with the same rule.
+pub(crate) fn negation_rule(node: Operator) -> Result<(Law, Operator), Operator> {
+    let not = Operator::not;
+    match node {
+        Operator::Not(c) => match *c {
+            Operator::Not(a) => Ok((Law::DoubleNegation, *a)),
+            Operator::Constant(b) => Ok((Law::NegatedConstant, Operator::Constant(!b))),
+            Operator::And(a, b) => Ok((Law::DeMorgan, Operator::or(not(*a), not(*b)))),
+            Operator::Or(a, b) => Ok((Law::DeMorgan, Operator::and(not(*a), not(*b)))),
+            Operator::Implies(a, b) => Ok((Law::NegatedImplication, Operator::and(*a, not(*b)))),
+            Operator::Xor(a, b) => Ok((Law::NegatedExclusiveOr, Operator::Equals(a, b))),
+            Operator::Equals(a, b) => Ok((Law::NegatedEquivalence, Operator::Xor(a, b))),
+            c @ Operator::Operand(_) => Err(not(c)),
+        },
+        Operator::Implies(a, b) => Ok((Law::ImplicationElimination, Operator::or(not(*a), *b))),
+        Operator::Equals(a, b) => Ok((
+            Law::EquivalenceElimination,
+            Operator::or(
+                Operator::and(*a.clone(), *b.clone()),
+                Operator::and(not(*a), not(*b)),
             ),
-            Operator::And(a, b) => Operator::with_two(
-                '&',
-                a.to_negation_normal_form(),
-                b.to_negation_normal_form(),
-            ),
-            Operator::Or(a, b) => Operator::with_two(
-                '|',
-                a.to_negation_normal_form(),
-                b.to_negation_normal_form(),
+        )),
+        Operator::Xor(a, b) => Ok((
+            Law::ExclusiveOrElimination,
+            Operator::or(
+                Operator::and(not(*a.clone()), *b.clone()),
+                Operator::and(*a, not(*b)),
             ),
-            Operator::Operand(_) => self,
+        )),
+        node => Err(node),
+    }
+}
+
+impl Operator {
+    pub fn to_negation_normal_form(self) -> Operator {
+        self.negation_normal_form_traced(&mut Trace::default())
+    }
+
+    // Rules apply on the way down, except that a negated Xor or Equals gets
+    // its operands converted before the negation is pushed in.
+    pub(crate) fn negation_normal_form_traced(self, trace: &mut Trace) -> Operator {
+        let node = match self {
+            Operator::Not(c) if matches!(*c, Operator::Xor(_, _) | Operator::Equals(_, _)) => {
+                let c = trace.child(0, |trace| {
+                    map_children(*c, trace, Operator::negation_normal_form_traced)
+                });
+                Operator::not(c)
+            }
+            node => node,
+        };
+        match negation_rule(node) {
+            Ok((law, node)) => {
+                trace.record(law);
+                node.negation_normal_form_traced(trace)
+            }
+            Err(node) => map_children(node, trace, Operator::negation_normal_form_traced),
         }
     }
 }
@@ -92,6 +74,7 @@ pub fn negation_normal_form(formula: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::truth_table::tests::truth_tables_equal;
 
     #[test]
     fn negation_normal_form_works_with_empty_string() {
@@ -201,7 +184,8 @@ mod tests {
     fn negation_normal_form_works_with_negated_implies() {
         let result = negation_normal_form("AB>!");
 
-        assert_eq!(result, "AB!|");
+        assert_eq!(result, "AB!&");
+        truth_tables_equal("AB>!", &result);
     }
 
     #[test]
@@ -218,4 +202,15 @@ mod tests {
         );
         assert_eq!(operators.to_negation_normal_form(), expected_operator_tree);
     }
+
+    #[test]
+    fn negations_of_compound_operands_are_pushed_down() {
+        for original in ["AB=C>", "AB&C=!", "AB^C>!", "AB|C^"] {
+            let result = negation_normal_form(original);
+
+            assert!(!result.contains(|c| "^=>".contains(c)), "{}", result);
+            assert!(!result.contains("&!") && !result.contains("|!") && !result.contains("!!"));
+            truth_tables_equal(original, &result);
+        }
+    }
 }
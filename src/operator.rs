@@ -1,3 +1,5 @@
+use std::fmt;
+
 #[derive(PartialEq, Debug, Clone, Hash, Eq)]
 pub enum Operator {
     And(Box<Operator>, Box<Operator>),
@@ -7,6 +9,7 @@ pub enum Operator {
     Equals(Box<Operator>, Box<Operator>),
     Not(Box<Operator>),
     Operand(char),
+    Constant(bool),
 }
 
 impl Operator {
@@ -33,10 +36,33 @@ impl Operator {
         Operator::with_two('|', a, b)
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn not(a: Operator) -> Operator {
         Operator::Not(Box::new(a))
     }
 
+    pub fn literal(c: char, positive: bool) -> Operator {
+        if positive {
+            Operator::operand(c)
+        } else {
+            Operator::not(Operator::operand(c))
+        }
+    }
+
+    pub fn conjunction<I: IntoIterator<Item = Operator>>(operators: I) -> Operator {
+        operators
+            .into_iter()
+            .reduce(Operator::and)
+            .unwrap_or(Operator::Constant(true))
+    }
+
+    pub fn disjunction<I: IntoIterator<Item = Operator>>(operators: I) -> Operator {
+        operators
+            .into_iter()
+            .reduce(Operator::or)
+            .unwrap_or(Operator::Constant(false))
+    }
+
     pub fn from_formula(formula: &str) -> Option<Operator> {
         let mut stack: Vec<Operator> = Vec::new();
 
@@ -46,6 +72,8 @@ impl Operator {
                 continue;
             }
             match token {
+                '1' => stack.push(Operator::Constant(true)),
+                '0' => stack.push(Operator::Constant(false)),
                 '!' => {
                     let operand = stack.pop().expect("No operand to negate");
                     let node = Operator::not(operand);
@@ -65,15 +93,102 @@ impl Operator {
         stack.pop()
     }
 
-    pub fn to_string(&self) -> String {
+    pub fn operands(&self) -> Vec<char> {
+        let mut operands = vec![];
+        self.collect_operands(&mut operands);
+        operands
+    }
+
+    fn collect_operands(&self, acc: &mut Vec<char>) {
         match self {
-            Operator::And(a, b) => format!("{}{}&", a.to_string(), b.to_string()),
-            Operator::Or(a, b) => format!("{}{}|", a.to_string(), b.to_string()),
-            Operator::Xor(a, b) => format!("{}{}^", a.to_string(), b.to_string()),
-            Operator::Implies(a, b) => format!("{}{}>", a.to_string(), b.to_string()),
-            Operator::Equals(a, b) => format!("{}{}=", a.to_string(), b.to_string()),
-            Operator::Not(c) => format!("{}!", c.to_string()),
+            Operator::And(a, b)
+            | Operator::Or(a, b)
+            | Operator::Xor(a, b)
+            | Operator::Implies(a, b)
+            | Operator::Equals(a, b) => {
+                a.collect_operands(acc);
+                b.collect_operands(acc);
+            }
+            Operator::Not(a) => a.collect_operands(acc),
+            Operator::Operand(c) => {
+                if !acc.contains(c) {
+                    acc.push(*c);
+                }
+            }
+            Operator::Constant(_) => {}
+        }
+    }
+
+    pub fn to_infix(&self) -> String {
+        let wrap = |a: &Operator| match a {
+            Operator::Operand(_) | Operator::Constant(_) | Operator::Not(_) => a.to_infix(),
+            _ => format!("({})", a.to_infix()),
+        };
+        match self {
+            Operator::And(a, b) => format!("{} & {}", wrap(a), wrap(b)),
+            Operator::Or(a, b) => format!("{} | {}", wrap(a), wrap(b)),
+            Operator::Xor(a, b) => format!("{} ^ {}", wrap(a), wrap(b)),
+            Operator::Implies(a, b) => format!("{} => {}", wrap(a), wrap(b)),
+            Operator::Equals(a, b) => format!("{} <=> {}", wrap(a), wrap(b)),
+            Operator::Not(a) => format!("!{}", wrap(a)),
             Operator::Operand(c) => c.to_string(),
+            Operator::Constant(b) => (if *b { "1" } else { "0" }).to_string(),
+        }
+    }
+
+    pub fn to_latex(&self) -> String {
+        let wrap = |a: &Operator| match a {
+            Operator::Operand(_) | Operator::Constant(_) | Operator::Not(_) => a.to_latex(),
+            _ => format!("({})", a.to_latex()),
+        };
+        match self {
+            Operator::And(a, b) => format!("{} \\land {}", wrap(a), wrap(b)),
+            Operator::Or(a, b) => format!("{} \\lor {}", wrap(a), wrap(b)),
+            Operator::Xor(a, b) => format!("{} \\oplus {}", wrap(a), wrap(b)),
+            Operator::Implies(a, b) => format!("{} \\rightarrow {}", wrap(a), wrap(b)),
+            Operator::Equals(a, b) => format!("{} \\leftrightarrow {}", wrap(a), wrap(b)),
+            Operator::Not(a) => format!("\\lnot {}", wrap(a)),
+            Operator::Operand(c) => c.to_string(),
+            Operator::Constant(b) => (if *b { "\\top" } else { "\\bot" }).to_string(),
+        }
+    }
+
+    pub fn subformulas(&self) -> Vec<&Operator> {
+        let mut subformulas = vec![];
+        self.collect_subformulas(&mut subformulas);
+        subformulas
+    }
+
+    fn collect_subformulas<'a>(&'a self, acc: &mut Vec<&'a Operator>) {
+        match self {
+            Operator::And(a, b)
+            | Operator::Or(a, b)
+            | Operator::Xor(a, b)
+            | Operator::Implies(a, b)
+            | Operator::Equals(a, b) => {
+                a.collect_subformulas(acc);
+                b.collect_subformulas(acc);
+            }
+            Operator::Not(a) => a.collect_subformulas(acc),
+            Operator::Operand(_) | Operator::Constant(_) => {}
+        }
+        if !acc.contains(&self) {
+            acc.push(self);
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Operator::And(a, b) => write!(f, "{}{}&", a, b),
+            Operator::Or(a, b) => write!(f, "{}{}|", a, b),
+            Operator::Xor(a, b) => write!(f, "{}{}^", a, b),
+            Operator::Implies(a, b) => write!(f, "{}{}>", a, b),
+            Operator::Equals(a, b) => write!(f, "{}{}=", a, b),
+            Operator::Not(c) => write!(f, "{}!", c),
+            Operator::Operand(c) => write!(f, "{}", c),
+            Operator::Constant(b) => write!(f, "{}", if *b { 1 } else { 0 }),
         }
     }
 }
@@ -174,4 +289,75 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn constants_are_parsed_and_printed() {
+        let tree = Operator::from_formula("A1&0|").unwrap();
+
+        assert_eq!(
+            tree,
+            Operator::or(
+                Operator::and(Operator::operand('A'), Operator::Constant(true)),
+                Operator::Constant(false)
+            )
+        );
+        assert_eq!(tree.to_string(), "A1&0|");
+        assert_eq!(tree.operands(), vec!['A']);
+    }
+
+    #[test]
+    fn infix_notation_adds_parentheses() {
+        let tree = Operator::from_formula("AB&C!|D>E=").unwrap();
+
+        assert_eq!(tree.to_infix(), "(((A & B) | !C) => D) <=> E");
+        assert_eq!(
+            Operator::from_formula("AB|!").unwrap().to_infix(),
+            "!(A | B)"
+        );
+    }
+
+    #[test]
+    fn latex_notation_uses_math_symbols() {
+        let tree = Operator::from_formula("AB&C!|D>1=").unwrap();
+
+        assert_eq!(
+            tree.to_latex(),
+            "(((A \\land B) \\lor \\lnot C) \\rightarrow D) \\leftrightarrow \\top"
+        );
+        assert_eq!(
+            Operator::from_formula("AB^!").unwrap().to_latex(),
+            "\\lnot (A \\oplus B)"
+        );
+    }
+
+    #[test]
+    fn subformulas_are_listed_in_evaluation_order() {
+        let tree = Operator::from_formula("AB&AB&!|").unwrap();
+        let subformulas: Vec<String> = tree.subformulas().iter().map(|s| s.to_string()).collect();
+
+        assert_eq!(subformulas, vec!["A", "B", "AB&", "AB&!", "AB&AB&!|"]);
+    }
+
+    #[test]
+    fn zero_and_one_are_constants() {
+        assert_eq!(Operator::from_formula("1"), Some(Operator::Constant(true)));
+        assert_eq!(
+            Operator::from_formula("0!"),
+            Some(Operator::not(Operator::Constant(false)))
+        );
+        assert!(Operator::from_formula("10>").unwrap().operands().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid operator")]
+    fn other_digits_are_not_operands() {
+        Operator::from_formula("AB2");
+    }
+
+    #[test]
+    fn operands_are_listed_in_order_of_appearance() {
+        let tree = Operator::from_formula("CAB&|A!C=&").unwrap();
+
+        assert_eq!(tree.operands(), vec!['C', 'A', 'B']);
+    }
 }
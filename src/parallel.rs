@@ -0,0 +1,245 @@
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+use crate::truth_table::{Row, TruthTable};
+
+const CHUNK_ROWS: u64 = 1 << 14;
+const CHUNK_BLOCKS: u64 = 1 << 10;
+
+pub fn available_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+// Splits `0..end` into chunks and hands out their results in chunk order.
+// Worker `w` of `threads` long-lived workers computes chunks `w`,
+// `w + threads`, ... and sends each through its own channel of capacity one,
+// so at most two chunks per worker are held in memory.
+struct Batches<T> {
+    receivers: Vec<Receiver<Vec<T>>>,
+    workers: Vec<JoinHandle<()>>,
+    chunks: u64,
+    next: u64,
+    buffer: std::vec::IntoIter<T>,
+}
+
+impl<T: Send + 'static> Batches<T> {
+    fn new<F>(end: u64, chunk: u64, threads: usize, compute: F) -> Batches<T>
+    where
+        F: Fn(u64, u64) -> Vec<T> + Send + Sync + 'static,
+    {
+        if threads == 0 {
+            panic!("Need at least one thread");
+        }
+        let chunks = end.div_ceil(chunk);
+        let compute = Arc::new(compute);
+        let (receivers, workers) = (0..(threads as u64).min(chunks))
+            .map(|worker| {
+                let (sender, receiver) = mpsc::sync_channel(1);
+                let compute = Arc::clone(&compute);
+                let handle = thread::spawn(move || {
+                    for i in (worker..chunks).step_by(threads) {
+                        let start = i * chunk;
+                        if sender
+                            .send(compute(start, (start + chunk).min(end)))
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                });
+                (receiver, handle)
+            })
+            .unzip();
+        Batches {
+            receivers,
+            workers,
+            chunks,
+            next: 0,
+            buffer: vec![].into_iter(),
+        }
+    }
+}
+
+impl<T> Iterator for Batches<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            if let Some(item) = self.buffer.next() {
+                return Some(item);
+            }
+            if self.next >= self.chunks {
+                return None;
+            }
+            let receiver = &self.receivers[(self.next % self.receivers.len() as u64) as usize];
+            self.buffer = receiver.recv().expect("Worker thread panicked").into_iter();
+            self.next += 1;
+        }
+    }
+}
+
+// Dropping the receivers stops the workers at their next send.
+impl<T> Drop for Batches<T> {
+    fn drop(&mut self) {
+        self.receivers.clear();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+// Rows in the table's own order, identical to `table.rows()`.
+pub fn par_rows(table: &TruthTable, threads: usize) -> impl Iterator<Item = Row> + '_ {
+    let table = Arc::new(table.clone());
+    Batches::new(table.len(), CHUNK_ROWS, threads, move |start, end| {
+        table
+            .rows_from(start)
+            .take((end - start) as usize)
+            .collect()
+    })
+}
+
+// Indices of the rows where the formula is true, in increasing index order
+// whatever the table's row order is.
+pub fn par_models(table: &TruthTable, threads: usize) -> impl Iterator<Item = u64> + '_ {
+    let table = Arc::new(table.clone());
+    Batches::new(table.blocks(), CHUNK_BLOCKS, threads, move |start, end| {
+        let mut models = vec![];
+        for block in start..end {
+            let mut bits = table.block(block);
+            while bits != 0 {
+                models.push(block * 64 + bits.trailing_zeros() as u64);
+                bits &= bits - 1;
+            }
+        }
+        models
+    })
+}
+
+pub fn par_count_models(table: &TruthTable, threads: usize) -> u64 {
+    if threads == 0 {
+        panic!("Need at least one thread");
+    }
+    let blocks = table.blocks();
+    let per_thread = blocks.div_ceil(threads as u64);
+    thread::scope(|s| {
+        let handles: Vec<_> = (0..threads as u64)
+            .map(|i| (i * per_thread, ((i + 1) * per_thread).min(blocks)))
+            .filter(|(start, end)| start < end)
+            .map(|(start, end)| {
+                s.spawn(move || {
+                    (start..end)
+                        .map(|block| table.block(block).count_ones() as u64)
+                        .sum::<u64>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("Worker thread panicked"))
+            .sum()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::truth_table::RowOrder;
+
+    const FORMULA: &str = "ABCDEFGHIJKLMNOP&|^&|>!=^&|^&|>!=";
+
+    #[test]
+    fn parallel_rows_keep_the_table_order() {
+        for order in [
+            RowOrder::Binary,
+            RowOrder::ReverseBinary,
+            RowOrder::Gray,
+            RowOrder::Weight,
+        ] {
+            let table = TruthTable::from_formula("ABCDEFGHIJKLMNO&|^&|>!=^&|^&|>!")
+                .unwrap()
+                .with_order(order);
+            let sequential: Vec<u64> = table.rows().map(|row| row.index).collect();
+            let parallel: Vec<u64> = par_rows(&table, 3).map(|row| row.index).collect();
+
+            assert_eq!(parallel, sequential);
+        }
+    }
+
+    #[test]
+    fn parallel_rows_by_weight_start_every_chunk_in_place() {
+        let table = TruthTable::from_formula(FORMULA)
+            .unwrap()
+            .with_order(RowOrder::Weight);
+        let sequential: Vec<u64> = table.rows().map(|row| row.index).collect();
+        let parallel: Vec<u64> = par_rows(&table, 4).map(|row| row.index).collect();
+
+        assert!(table.len() > 2 * CHUNK_ROWS);
+        assert_eq!(parallel, sequential);
+    }
+
+    #[test]
+    fn workers_are_spawned_once() {
+        let batches = Batches::new(1 << 20, 16, 3, |start, end| (start..end).collect());
+
+        assert_eq!(batches.workers.len(), 3);
+        assert!(batches.eq(0..1 << 20));
+        assert_eq!(
+            Batches::new(10, 16, 8, |start, end| (start..end).collect())
+                .workers
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn dropping_rows_early_stops_the_workers() {
+        let table =
+            TruthTable::from_formula("ABCDEFGHIJKLMNOPQRSTUVWX&&&&&&&&&&&&&&&&&&&&&&&").unwrap();
+        let first: Vec<u64> = par_rows(&table, 4).take(3).map(|row| row.index).collect();
+
+        assert_eq!(first, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn parallel_models_match_minterms() {
+        let table = TruthTable::from_formula(FORMULA).unwrap();
+        let models: Vec<u64> = par_models(&table, 4).collect();
+
+        assert_eq!(models, table.minterms());
+    }
+
+    #[test]
+    fn counting_models_matches_sequential_count() {
+        let table = TruthTable::from_formula(FORMULA).unwrap();
+        let expected = table.rows().filter(|row| row.result).count() as u64;
+
+        for threads in [1, 2, 5] {
+            assert_eq!(par_count_models(&table, threads), expected);
+        }
+    }
+
+    #[test]
+    fn counting_works_for_small_tables() {
+        let table = TruthTable::from_formula("AB|").unwrap();
+
+        assert_eq!(par_count_models(&table, 8), 3);
+        assert_eq!(par_models(&table, 8).collect::<Vec<u64>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn counting_large_tables_works() {
+        let table =
+            TruthTable::from_formula("ABCDEFGHIJKLMNOPQRSTUVWX&&&&&&&&&&&&&&&&&&&&&&&!").unwrap();
+
+        assert_eq!(par_count_models(&table, available_threads()), (1 << 24) - 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_threads_panics() {
+        let table = TruthTable::from_formula("A").unwrap();
+        par_count_models(&table, 0);
+    }
+}
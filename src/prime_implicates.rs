@@ -0,0 +1,170 @@
+use crate::cnf::{Clause, Cnf};
+use crate::cube::{Cover, Cube};
+use crate::operator::Operator;
+
+// Adds `clause` unless a kept clause subsumes it, and drops the kept clauses
+// it subsumes.
+fn insert(kept: &mut Vec<Clause>, clause: Clause) {
+    if kept.iter().any(|c| c.subsumes(&clause)) {
+        return;
+    }
+    kept.retain(|c| !clause.subsumes(c));
+    kept.push(clause);
+}
+
+// Resolves pairs of clauses until every resolvent is subsumed by a kept
+// clause. The clauses left are the prime implicates of the conjunction.
+pub fn resolution_closure(clauses: &[Clause]) -> Vec<Clause> {
+    let mut kept = vec![];
+    for clause in clauses.iter().cloned().filter_map(Clause::normalize) {
+        insert(&mut kept, clause);
+    }
+    loop {
+        let resolvent = kept
+            .iter()
+            .enumerate()
+            .flat_map(|(i, a)| kept[i + 1..].iter().filter_map(|b| a.resolve(b)))
+            .find(|r| !kept.iter().any(|c| c.subsumes(r)));
+        match resolvent {
+            Some(r) => insert(&mut kept, r),
+            None => break,
+        }
+    }
+    kept.sort_by_key(|c| (c.len(), c.literals().to_vec()));
+    kept
+}
+
+// The strongest clauses the formula implies, from the clauses of its
+// conjunctive normal form. An unsatisfiable formula gives the empty clause.
+pub fn prime_implicates(operator: &Operator) -> Cnf {
+    let cnf = Cnf::from_operator(operator);
+    let mut implicates = Cnf::new(cnf.operands());
+    for clause in resolution_closure(cnf.clauses()) {
+        implicates.add_clause(clause);
+    }
+    implicates
+}
+
+// All prime implicants, each one the negation of a prime implicate of the
+// negated formula, so consensus runs as resolution on clauses.
+pub fn blake_canonical_form(operator: &Operator) -> Cover {
+    let implicates = prime_implicates(&Operator::not(operator.clone()));
+    let n = implicates.operands().len();
+    if n > 64 {
+        panic!("Cubes have room for 64 operands, not {}", n);
+    }
+    let mut cubes: Vec<Cube> = implicates
+        .clauses()
+        .iter()
+        .map(|clause| {
+            let (mut mask, mut value) = (0, 0);
+            for literal in clause.literals() {
+                let bit = 1u64 << (n - 1 - literal.variable());
+                mask |= bit;
+                if !literal.is_positive() {
+                    value |= bit;
+                }
+            }
+            Cube::new(mask, value)
+        })
+        .collect();
+    cubes.sort_by_key(|cube| {
+        (
+            cube.literal_count(),
+            std::cmp::Reverse(cube.value),
+            cube.mask,
+        )
+    });
+    Cover::new(implicates.operands(), cubes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::incomplete::IncompleteFunction;
+    use crate::quine_mccluskey::prime_implicants;
+    use crate::table_diff::equivalent;
+
+    fn tree(formula: &str) -> Operator {
+        Operator::from_formula(formula).unwrap()
+    }
+
+    #[test]
+    fn transitivity_is_found() {
+        let implicates = prime_implicates(&tree("AB>BC>&"));
+
+        assert_eq!(
+            implicates.clauses(),
+            &[
+                Clause::from_dimacs(&[-1, 2]),
+                Clause::from_dimacs(&[-1, 3]),
+                Clause::from_dimacs(&[-2, 3]),
+            ]
+        );
+    }
+
+    #[test]
+    fn subsumed_clauses_are_dropped() {
+        let implicates = prime_implicates(&tree("AB|AB!|&C|"));
+
+        assert_eq!(implicates.clauses(), &[Clause::from_dimacs(&[1, 3])]);
+    }
+
+    #[test]
+    fn constants_give_trivial_implicates() {
+        assert_eq!(
+            prime_implicates(&tree("AA!&")).clauses(),
+            &[Clause::default()]
+        );
+        assert!(prime_implicates(&tree("AA!|")).clauses().is_empty());
+        assert!(blake_canonical_form(&tree("AA!&")).cubes().is_empty());
+        assert_eq!(
+            blake_canonical_form(&tree("AA!|")).cubes(),
+            &[Cube::new(0, 0)]
+        );
+    }
+
+    #[test]
+    fn consensus_terms_are_added() {
+        let blake = blake_canonical_form(&tree("AB&A!C&|"));
+
+        assert_eq!(
+            blake.cubes(),
+            &[
+                Cube::new(0b110, 0b110),
+                Cube::new(0b011, 0b011),
+                Cube::new(0b101, 0b001),
+            ]
+        );
+    }
+
+    #[test]
+    fn blake_form_matches_quine_mccluskey() {
+        for formula in ["AB|C&A!B!&C!|&", "AB=C>", "AB^C|D&", "AB>C=D!|"] {
+            let operator = tree(formula);
+            let on =
+                IncompleteFunction::from_formulas(&operator, &Operator::Constant(false)).on_set();
+            let mut expected = prime_implicants(operator.operands().len(), &on, &[]);
+            let blake = blake_canonical_form(&operator);
+            let mut cubes = blake.cubes().to_vec();
+            expected.sort();
+            cubes.sort();
+
+            assert_eq!(cubes, expected, "{}", formula);
+            assert!(equivalent(&operator, &blake.to_operator()), "{}", formula);
+        }
+    }
+
+    #[test]
+    fn prime_implicates_are_equivalent() {
+        for formula in ["AB|C&A!B!&C!|&", "AB=C>", "AB^C|D&", "AB&C!D|>"] {
+            let operator = tree(formula);
+
+            assert!(
+                equivalent(&operator, &prime_implicates(&operator).to_operator()),
+                "{}",
+                formula
+            );
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use crate::operator::Operator;
+
+fn check_probability(c: char, p: f64) -> f64 {
+    if !(0.0..=1.0).contains(&p) {
+        panic!("Probability for {} must be between 0 and 1, got {}", c, p);
+    }
+    p
+}
+
+// Evaluates as far as the partial assignment allows, None means the value
+// still depends on an unassigned operand.
+fn partial_solve(node: &Operator, values: &HashMap<char, bool>) -> Option<bool> {
+    match node {
+        Operator::And(a, b) => match (partial_solve(a, values), partial_solve(b, values)) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ => None,
+        },
+        Operator::Or(a, b) => match (partial_solve(a, values), partial_solve(b, values)) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (Some(false), Some(false)) => Some(false),
+            _ => None,
+        },
+        Operator::Implies(a, b) => match (partial_solve(a, values), partial_solve(b, values)) {
+            (Some(false), _) | (_, Some(true)) => Some(true),
+            (Some(true), Some(false)) => Some(false),
+            _ => None,
+        },
+        Operator::Xor(a, b) => Some(partial_solve(a, values)? ^ partial_solve(b, values)?),
+        Operator::Equals(a, b) => Some(partial_solve(a, values)? == partial_solve(b, values)?),
+        Operator::Not(a) => partial_solve(a, values).map(|a| !a),
+        Operator::Operand(c) => values.get(c).copied(),
+        Operator::Constant(b) => Some(*b),
+    }
+}
+
+fn shannon_expansion<F: Fn(char) -> f64>(
+    node: &Operator,
+    operands: &[char],
+    values: &mut HashMap<char, bool>,
+    p: &F,
+) -> f64 {
+    if let Some(result) = partial_solve(node, values) {
+        return if result { 1.0 } else { 0.0 };
+    }
+    let (&c, rest) = operands
+        .split_first()
+        .expect("No operand left to expand on");
+    let p_true = check_probability(c, p(c));
+    values.insert(c, true);
+    let when_true = shannon_expansion(node, rest, values, p);
+    values.insert(c, false);
+    let when_false = shannon_expansion(node, rest, values, p);
+    values.remove(&c);
+    p_true * when_true + (1.0 - p_true) * when_false
+}
+
+pub fn probability<F: Fn(char) -> f64>(operator: &Operator, p: F) -> f64 {
+    let operands = operator.operands();
+    shannon_expansion(operator, &operands, &mut HashMap::new(), &p)
+}
+
+// SplitMix64, small and good enough to draw samples from a fixed seed.
+pub(crate) struct Rng(pub(crate) u64);
+
+impl Rng {
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+pub fn estimate_probability<F: Fn(char) -> f64>(
+    operator: &Operator,
+    p: F,
+    samples: usize,
+    seed: u64,
+) -> f64 {
+    if samples == 0 {
+        panic!("Need at least one sample");
+    }
+    let operands: Vec<(char, f64)> = operator
+        .operands()
+        .into_iter()
+        .map(|c| (c, check_probability(c, p(c))))
+        .collect();
+    let mut rng = Rng(seed);
+    let mut values = HashMap::new();
+    let mut hits = 0;
+    for _ in 0..samples {
+        for (c, p_true) in operands.iter() {
+            values.insert(*c, rng.next_f64() < *p_true);
+        }
+        if partial_solve(operator, &values).expect("All operands are assigned") {
+            hits += 1;
+        }
+    }
+    hits as f64 / samples as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64, epsilon: f64) {
+        assert!((a - b).abs() < epsilon, "{} is not close to {}", a, b);
+    }
+
+    fn p(c: char) -> f64 {
+        match c {
+            'A' => 0.3,
+            'B' => 0.8,
+            'C' => 0.5,
+            _ => 0.1,
+        }
+    }
+
+    #[test]
+    fn probability_of_single_operand() {
+        let tree = Operator::from_formula("A").unwrap();
+
+        assert_close(probability(&tree, p), 0.3, 1e-12);
+    }
+
+    #[test]
+    fn probability_of_negation() {
+        let tree = Operator::from_formula("A!").unwrap();
+
+        assert_close(probability(&tree, p), 0.7, 1e-12);
+    }
+
+    #[test]
+    fn probability_of_conjunction_and_disjunction() {
+        let and = Operator::from_formula("AB&").unwrap();
+        let or = Operator::from_formula("AB|").unwrap();
+
+        assert_close(probability(&and, p), 0.24, 1e-12);
+        assert_close(probability(&or, p), 1.0 - 0.7 * 0.2, 1e-12);
+    }
+
+    #[test]
+    fn probability_of_tautology_and_contradiction() {
+        let tautology = Operator::from_formula("AA>").unwrap();
+        let contradiction = Operator::from_formula("AA^").unwrap();
+
+        assert_close(probability(&tautology, p), 1.0, 1e-12);
+        assert_close(probability(&contradiction, p), 0.0, 1e-12);
+    }
+
+    #[test]
+    fn probability_with_shared_operands() {
+        let tree = Operator::from_formula("AB&AC&|").unwrap();
+
+        assert_close(probability(&tree, p), 0.3 * (1.0 - 0.2 * 0.5), 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn probability_out_of_range_panics() {
+        let tree = Operator::from_formula("A").unwrap();
+        probability(&tree, |_| 1.5);
+    }
+
+    #[test]
+    fn estimate_is_close_to_exact_probability() {
+        let tree = Operator::from_formula("AB^C|").unwrap();
+        let exact = probability(&tree, p);
+        let estimate = estimate_probability(&tree, p, 100_000, 42);
+
+        assert_close(estimate, exact, 0.01);
+    }
+
+    #[test]
+    fn estimate_is_deterministic_for_a_seed() {
+        let tree = Operator::from_formula("AB&C|").unwrap();
+
+        assert_eq!(
+            estimate_probability(&tree, p, 1000, 7),
+            estimate_probability(&tree, p, 1000, 7)
+        );
+    }
+}
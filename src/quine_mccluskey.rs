@@ -0,0 +1,239 @@
+use std::collections::HashSet;
+
+use crate::cube::{Cover, Cube};
+use crate::incomplete::IncompleteFunction;
+use crate::operator::Operator;
+
+// Merges cubes that differ in a single literal until nothing merges anymore.
+// The cubes that never merged are the prime implicants.
+pub fn prime_implicants(n: usize, on: &[u64], dont_care: &[u64]) -> Vec<Cube> {
+    let mut current: HashSet<Cube> = on
+        .iter()
+        .chain(dont_care)
+        .map(|&index| Cube::minterm(n, index))
+        .collect();
+    let mut primes: Vec<Cube> = vec![];
+    while !current.is_empty() {
+        let mut next = HashSet::new();
+        let mut merged = HashSet::new();
+        for cube in &current {
+            for bit in (0..n).map(|i| 1u64 << i).filter(|bit| cube.mask & bit != 0) {
+                let partner = Cube::new(cube.mask, cube.value ^ bit);
+                if current.contains(&partner) {
+                    merged.insert(*cube);
+                    next.insert(Cube::new(cube.mask & !bit, cube.value));
+                }
+            }
+        }
+        primes.extend(current.iter().filter(|cube| !merged.contains(cube)));
+        current = next;
+    }
+    primes.sort_by_key(|cube| {
+        (
+            cube.literal_count(),
+            std::cmp::Reverse(cube.value),
+            cube.mask,
+        )
+    });
+    primes
+}
+
+// Multiplies the products by the sum of the `covering` primes and drops the
+// products that contain another one.
+fn multiply(products: Vec<Vec<usize>>, covering: &[usize]) -> Vec<Vec<usize>> {
+    let mut next: Vec<Vec<usize>> = vec![];
+    for product in products {
+        if product.iter().any(|p| covering.contains(p)) {
+            next.push(product);
+            continue;
+        }
+        for &prime in covering {
+            let mut product = product.clone();
+            product.push(prime);
+            product.sort_unstable();
+            next.push(product);
+        }
+    }
+    next.sort_by_key(|product| product.len());
+    let mut kept: Vec<Vec<usize>> = vec![];
+    for product in next {
+        if !kept.iter().any(|k| k.iter().all(|p| product.contains(p))) {
+            kept.push(product);
+        }
+    }
+    kept
+}
+
+// Essential primes first, then Petrick's method over the rows they leave
+// uncovered. Among the products with the fewest cubes the one with the
+// fewest literals wins.
+pub fn minimum_cover(primes: &[Cube], on: &[u64]) -> Vec<Cube> {
+    let covering = |index: u64| -> Vec<usize> {
+        (0..primes.len())
+            .filter(|&p| primes[p].contains(index))
+            .collect()
+    };
+    let mut chosen: Vec<usize> = vec![];
+    for &index in on {
+        if let [only] = covering(index)[..] {
+            if !chosen.contains(&only) {
+                chosen.push(only);
+            }
+        }
+    }
+    let mut products = vec![vec![]];
+    for &index in on {
+        let covering = covering(index);
+        if covering.is_empty() {
+            panic!("Row {} is not covered by any prime implicant", index);
+        }
+        if !chosen.iter().any(|p| covering.contains(p)) {
+            products = multiply(products, &covering);
+        }
+    }
+    let best = products
+        .into_iter()
+        .min_by_key(|product| {
+            (
+                product.len(),
+                product
+                    .iter()
+                    .map(|&p| primes[p].literal_count())
+                    .sum::<u32>(),
+            )
+        })
+        .unwrap_or_default();
+    chosen.extend(best);
+    chosen.sort_unstable();
+    chosen.into_iter().map(|p| primes[p]).collect()
+}
+
+pub fn minimize_incomplete(function: &IncompleteFunction) -> Cover {
+    let n = function.operands().len();
+    let on = function.on_set();
+    let primes = prime_implicants(n, &on, &function.dont_care_set());
+    Cover::new(function.operands(), minimum_cover(&primes, &on))
+}
+
+pub fn minimize(operator: &Operator) -> Cover {
+    minimize_incomplete(&IncompleteFunction::from_formulas(
+        operator,
+        &Operator::Constant(false),
+    ))
+}
+
+pub fn minimal_sum_of_products(formula: &str) -> String {
+    match Operator::from_formula(formula) {
+        Some(operator) => minimize(&operator).to_operator().to_string(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::incomplete::Value;
+    use crate::probability::Rng;
+    use crate::table_diff::equivalent;
+
+    fn tree(formula: &str) -> Operator {
+        Operator::from_formula(formula).unwrap()
+    }
+
+    #[test]
+    fn prime_implicants_of_a_textbook_function() {
+        let primes = prime_implicants(4, &[4, 8, 10, 11, 12, 15], &[9, 14]);
+
+        assert_eq!(
+            primes,
+            vec![
+                Cube::new(0b1010, 0b1010),
+                Cube::new(0b1001, 0b1000),
+                Cube::new(0b1100, 0b1000),
+                Cube::new(0b0111, 0b0100),
+            ]
+        );
+    }
+
+    #[test]
+    fn minimum_cover_uses_dont_cares() {
+        let function =
+            IncompleteFunction::new(&['A', 'B', 'C', 'D'], &[4, 8, 10, 11, 12, 15], &[9, 14]);
+        let cover = minimize_incomplete(&function);
+
+        assert_eq!(cover.cubes().len(), 3);
+        assert_eq!(cover.literal_count(), 7);
+        assert!(function.is_implemented_by(&cover.to_operator()));
+    }
+
+    #[test]
+    fn petrick_resolves_cyclic_covers() {
+        let function = IncompleteFunction::new(&['A', 'B', 'C'], &[0, 1, 2, 5, 6, 7], &[]);
+        let cover = minimize_incomplete(&function);
+
+        assert_eq!(cover.cubes().len(), 3);
+        assert_eq!(cover.literal_count(), 6);
+    }
+
+    #[test]
+    fn random_incomplete_functions_are_covered() {
+        let mut rng = Rng(42);
+        for n in 1..=5 {
+            let operands: Vec<char> = ('A'..='Z').take(n).collect();
+            for _ in 0..40 {
+                let (mut on, mut dont_care) = (vec![], vec![]);
+                for index in 0..1u64 << n {
+                    match rng.next_u64() % 3 {
+                        0 => on.push(index),
+                        1 => dont_care.push(index),
+                        _ => {}
+                    }
+                }
+                let function = IncompleteFunction::new(&operands, &on, &dont_care);
+                let cover = minimize_incomplete(&function);
+
+                for index in 0..function.len() {
+                    match function.value(index) {
+                        Value::DontCare => {}
+                        value => assert_eq!(
+                            value == Value::True,
+                            cover.contains(index),
+                            "{:?} {:?} {}",
+                            on,
+                            dont_care,
+                            index
+                        ),
+                    }
+                }
+                assert!(function.is_implemented_by(&cover.to_operator()));
+            }
+        }
+    }
+
+    #[test]
+    fn minimal_forms_are_equivalent() {
+        for formula in ["AB|C&A!B!&C!|&", "AB=C>", "AB^C^D^", "ABCD&&&AB!C!D&&&|"] {
+            let operator = tree(formula);
+            let cover = minimize(&operator);
+
+            assert!(equivalent(&operator, &cover.to_operator()), "{}", formula);
+        }
+    }
+
+    #[test]
+    fn minimal_sum_of_products_works() {
+        assert_eq!(minimal_sum_of_products("AB&AB!&|"), "A");
+        assert_eq!(minimal_sum_of_products("AB&A!C&|BC&|"), "AB&A!C&|");
+        assert_eq!(minimal_sum_of_products("AA!&"), "0");
+        assert_eq!(minimal_sum_of_products("AA!|"), "1");
+    }
+
+    #[test]
+    fn twelve_operands_are_fine() {
+        let operator = tree("AB&CD&|EF&|GH&|IJ&|KL&|");
+        let cover = minimize(&operator);
+
+        assert_eq!(cover.cubes().len(), 6);
+        assert_eq!(cover.literal_count(), 12);
+    }
+}
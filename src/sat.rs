@@ -1,19 +1,115 @@
-use std::collections::HashSet;
-
+use crate::cnf::{Clause, Cnf, Literal};
+use crate::cnf_encoding::CnfEncoding;
 use crate::operator::Operator;
 
 pub fn sat(formula: &str) -> bool {
-    let operator = Operator::from_formula(formula);
-    if operator.is_none() {
-        return true;
-    }
-    let operator = operator.unwrap();
-    let operator = operator
-        .to_negation_normal_form()
-        .to_conjunctive_normal_form();
-    let mut clauses = operator.extract_clauses();
-    let mut assignments = HashSet::new();
-    Operator::dpll(&mut clauses, &mut assignments)
+    sat_with_encoding(formula, CnfEncoding::default())
+}
+
+pub fn sat_with_encoding(formula: &str, encoding: CnfEncoding) -> bool {
+    match Operator::from_formula(formula) {
+        Some(operator) => satisfying_assignment(&operator, encoding).is_some(),
+        None => true,
+    }
+}
+
+pub fn satisfying_assignment(
+    operator: &Operator,
+    encoding: CnfEncoding,
+) -> Option<Vec<(char, bool)>> {
+    let cnf = encoding.encode(operator);
+    solve(&cnf).map(|model| cnf.assignment(&model))
+}
+
+// A model of the clauses if there is one, `model[v]` is the value of
+// variable `v`.
+pub fn solve(cnf: &Cnf) -> Option<Vec<bool>> {
+    let mut assignment = vec![None; cnf.variables()];
+    if dpll(cnf.clauses(), &mut assignment) {
+        Some(assignment.into_iter().map(|v| v.unwrap_or(false)).collect())
+    } else {
+        None
+    }
+}
+
+fn value(assignment: &[Option<bool>], literal: Literal) -> Option<bool> {
+    assignment[literal.variable()].map(|v| v == literal.is_positive())
+}
+
+fn assign(assignment: &mut [Option<bool>], literal: Literal) {
+    assignment[literal.variable()] = Some(literal.is_positive());
+}
+
+// Assigns the last literal of every unit clause until nothing changes. Returns
+// false on a conflict, the assigned variables are pushed to `trail`.
+fn propagate(
+    clauses: &[Clause],
+    assignment: &mut [Option<bool>],
+    trail: &mut Vec<Literal>,
+) -> bool {
+    loop {
+        let mut changed = false;
+        for clause in clauses {
+            let mut open = None;
+            let mut count = 0;
+            if clause
+                .literals()
+                .iter()
+                .any(|&l| value(assignment, l) == Some(true))
+            {
+                continue;
+            }
+            for &literal in clause.literals() {
+                if value(assignment, literal).is_none() {
+                    open = Some(literal);
+                    count += 1;
+                }
+            }
+            match (count, open) {
+                (0, _) => return false,
+                (1, Some(literal)) => {
+                    assign(assignment, literal);
+                    trail.push(literal);
+                    changed = true;
+                }
+                _ => {}
+            }
+        }
+        if !changed {
+            return true;
+        }
+    }
+}
+
+fn dpll(clauses: &[Clause], assignment: &mut [Option<bool>]) -> bool {
+    let mut trail = vec![];
+    if propagate(clauses, assignment, &mut trail) {
+        let next = clauses
+            .iter()
+            .filter(|clause| {
+                !clause
+                    .literals()
+                    .iter()
+                    .any(|&l| value(assignment, l) == Some(true))
+            })
+            .flat_map(|clause| clause.literals().iter())
+            .find(|&&l| value(assignment, l).is_none())
+            .copied();
+        let Some(literal) = next else {
+            return true;
+        };
+        for choice in [literal, !literal] {
+            assign(assignment, choice);
+            if dpll(clauses, assignment) {
+                return true;
+            }
+            assignment[choice.variable()] = None;
+        }
+    }
+    for literal in trail {
+        assignment[literal.variable()] = None;
+    }
+    false
 }
 
 impl Operator {
@@ -47,95 +143,103 @@ impl Operator {
             _ => Operator::not(self.clone()),
         }
     }
-
-    fn dpll(clauses: &mut Vec<Vec<Operator>>, assignments: &mut HashSet<Operator>) -> bool {
-        if clauses.is_empty() {
-            return true;
-        }
-        if clauses.iter().any(|clause| clause.is_empty()) {
-            return false;
-        }
-
-        for clause in clauses.clone().iter() {
-            if clause.len() == 1 {
-                let literal = &clause[0];
-                let negated = literal.negate();
-                assignments.insert(literal.clone());
-                clauses.retain(|c| !c.contains(literal)); 
-                for c in clauses.iter_mut() {
-                    c.retain(|l| *l != negated); 
-                }
-                return Operator::dpll(clauses, assignments);
-            }
-        }
-
-        let literal = clauses[0][0].clone(); 
-        let negated = literal.negate();
-
-        let mut new_clauses_true = clauses.clone();
-        new_clauses_true.retain(|c| !c.contains(&literal)); 
-        for c in new_clauses_true.iter_mut() {
-            c.retain(|l| *l != negated); 
-        }
-        if Operator::dpll(&mut new_clauses_true, assignments) {
-            return true;
-        }
-
-        // Try assigning false to the literal
-        let mut new_clauses_false = clauses.clone();
-        new_clauses_false.retain(|c| !c.contains(&negated)); 
-        for c in new_clauses_false.iter_mut() {
-            c.retain(|l| *l != literal);
-        }
-        if Operator::dpll(&mut new_clauses_false, assignments) {
-            return true;
-        }
-
-        false
-    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::algebra::evaluate;
 
     #[test]
     fn empty_clause_is_satisfiable() {
-        assert_eq!(sat(""), true);
+        assert!(sat(""));
     }
 
     #[test]
     fn base_case() {
-        assert_eq!(sat("A"), true);
+        assert!(sat("A"));
     }
 
     #[test]
     fn test_sat() {
-        assert_eq!(sat("AB|"), true);
+        assert!(sat("AB|"));
     }
 
     #[test]
     fn sat_works_with_other_operators() {
-        assert_eq!(sat("AA^"), false);
+        assert!(!sat("AA^"));
     }
 
     #[test]
     fn single_literal_and_negation() {
-        assert_eq!(sat("AA!&"), false);
+        assert!(!sat("AA!&"));
     }
 
     #[test]
     fn disjunction_of_literals() {
-        assert_eq!(sat("AB|"), true);
+        assert!(sat("AB|"));
     }
 
     #[test]
     fn conjunction_of_literals_and_negations() {
-        assert_eq!(sat("AB!&"), true);
+        assert!(sat("AB!&"));
+    }
+
+    #[test]
+    fn constants_are_not_treated_as_operands() {
+        assert!(sat("1"));
+        assert!(!sat("0"));
+        assert!(!sat("A0&"));
+        assert!(sat("A!1|A&"));
     }
 
     #[test]
     fn complex_cnf_expression() {
-        assert_eq!(sat("AB|AC|&"), true);
+        assert!(sat("AB|AC|&"));
+    }
+
+    #[test]
+    fn long_xor_chains_are_solved() {
+        let mut formula: String = ('A'..='U').collect();
+        formula.push_str(&"^".repeat(20));
+
+        assert!(sat(&formula));
+        formula.push_str("A!&A&");
+        assert!(!sat(&formula));
+    }
+
+    #[test]
+    fn assignments_satisfy_the_formula() {
+        for formula in ["AB^C&", "AB>B!&", "AB=C^D|E&", "AB&C0|&", "AB|!C>A&"] {
+            let operator = Operator::from_formula(formula).unwrap();
+            for encoding in [CnfEncoding::Tseitin, CnfEncoding::PlaistedGreenbaum] {
+                let assignment = satisfying_assignment(&operator, encoding).unwrap();
+                let value = |c| assignment.iter().find(|(o, _)| *o == c).unwrap().1;
+
+                assert_eq!(assignment.len(), operator.operands().len());
+                assert!(evaluate(&operator, &value).unwrap(), "{}", formula);
+            }
+        }
+    }
+
+    #[test]
+    fn distributive_encoding_can_be_selected() {
+        assert!(sat_with_encoding("AB|AC|&", CnfEncoding::Distributive));
+        assert!(!sat_with_encoding("AB^AB=&", CnfEncoding::Distributive));
+        assert!(!sat_with_encoding("AB^AB=&", CnfEncoding::Tseitin));
+    }
+
+    #[test]
+    fn solver_returns_a_model() {
+        let mut cnf = Cnf::new(&['A', 'B', 'C']);
+        for clause in [&[1, 2][..], &[-1], &[-2, 3]] {
+            cnf.add_clause(Clause::from_dimacs(clause));
+        }
+        assert_eq!(solve(&cnf).unwrap(), vec![false, true, true]);
+
+        let mut cnf = Cnf::new(&['A']);
+        cnf.add_clause(Clause::from_dimacs(&[1]));
+        cnf.add_clause(Clause::from_dimacs(&[-1]));
+        assert_eq!(solve(&cnf), None);
     }
 }
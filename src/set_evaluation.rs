@@ -1,5 +1,7 @@
 use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
 
+use crate::algebra::{evaluate_in, FiniteSet};
 use crate::operator::Operator;
 
 fn operands_in_formula(formula: &str) -> Vec<char> {
@@ -16,53 +18,8 @@ fn operands_in_formula(formula: &str) -> Vec<char> {
     operands
 }
 
-fn solve(operator: &Operator, sets: HashMap<char, HashSet<i32>>) -> HashSet<i32> {
-    match operator {
-        Operator::Operand(c) => sets.get(c).unwrap().clone(),
-        Operator::Not(c) => {
-            let c = solve(c, sets.clone());
-            let all = sets.values().flatten().cloned().collect::<Vec<i32>>();
-            all.iter().filter(|x| !c.contains(x)).cloned().collect()
-        }
-        Operator::Or(a, b) => {
-            let a = solve(a, sets.clone());
-            let b = solve(b, sets.clone());
-            a.iter().chain(b.iter()).cloned().collect()
-        }
-        Operator::And(a, b) => {
-            let a = solve(a, sets.clone());
-            let b = solve(b, sets.clone());
-            a.iter().filter(|x| b.contains(x)).cloned().collect()
-        }
-        Operator::Xor(a, b) => {
-            let a = solve(a, sets.clone());
-            let b = solve(b, sets.clone());
-            let a: HashSet<i32> = a.into_iter().filter(|x| !b.contains(x)).collect();
-            let b: HashSet<i32> = b.into_iter().filter(|x| !a.contains(x)).collect();
-            a.into_iter().chain(b.into_iter()).collect()
-        }
-        Operator::Implies(a, b) => {
-            let a = solve(a, sets.clone());
-            let b = solve(b, sets.clone());
-            let is_subset = a.iter().all(|x| b.contains(&x));
-            if is_subset {
-                a
-            } else {
-                HashSet::new()
-            }
-        }
-        Operator::Equals(a, b) => {
-            let a = solve(a, sets.clone());
-            let b = solve(b, sets.clone());
-            if a == b {
-                a
-            } else {
-                HashSet::new()
-            }
-        }
-    }
-}
-
+// Sets are elements of the powerset of the union of all input sets, so the
+// complement of a set is taken relative to that union.
 pub fn eval_set(formula: &str, sets: Vec<Vec<i32>>) -> Vec<i32> {
     let operands = operands_in_formula(formula);
     if operands.len() != sets.len() {
@@ -73,15 +30,20 @@ pub fn eval_set(formula: &str, sets: Vec<Vec<i32>>) -> Vec<i32> {
         panic!("Empty formula");
     }
     let operator = operator.unwrap();
-    let set = solve(
-        &operator,
-        operands
-            .into_iter()
-            .zip(sets)
-            .map(|(c, values)| (c, HashSet::from_iter(values)))
-            .collect(),
-    );
-    set.into_iter().collect()
+    let universe: Rc<HashSet<i32>> = Rc::new(sets.iter().flatten().cloned().collect());
+    let values: HashMap<char, FiniteSet> = operands
+        .into_iter()
+        .zip(sets)
+        .map(|(c, values)| {
+            (
+                c,
+                FiniteSet::new(universe.clone(), HashSet::from_iter(values)),
+            )
+        })
+        .collect();
+    let empty = FiniteSet::new(universe, HashSet::new());
+    let set = evaluate_in(&operator, &empty, &|c| values[&c].clone());
+    set.elements().iter().cloned().collect()
 }
 
 #[cfg(test)]
@@ -157,21 +119,22 @@ mod tests {
 
         let sets = vec![vec![1], vec![1]];
         let result = eval_set("AB^", sets.clone());
-        assert_eq!(result, vec![1]);
+        assert_eq!(result, vec![]);
     }
 
     #[test]
     fn eval_set_works_with_implies() {
         let sets = vec![vec![1], vec![2]];
         let result = eval_set("AB>", sets.clone());
-        assert_eq!(result, vec![]);
+        assert_eq!(result, vec![2]);
 
         let sets = vec![vec![1, 2], vec![1]];
         let result = eval_set("AB>", sets.clone());
-        assert_eq!(result, vec![]);
+        assert_eq!(result, vec![1]);
 
         let sets = vec![vec![1], vec![1, 2]];
-        let result = eval_set("AB>", sets.clone());
-        assert_eq!(result, vec![1]);
+        let mut result = eval_set("AB>", sets.clone());
+        result.sort();
+        assert_eq!(result, vec![1, 2]);
     }
 }
@@ -0,0 +1,237 @@
+use crate::operator::Operator;
+
+fn is_complement(a: &Operator, b: &Operator) -> bool {
+    matches!(a, Operator::Not(inner) if **inner == *b)
+        || matches!(b, Operator::Not(inner) if **inner == *a)
+}
+
+fn same_kind(node: &Operator, and: bool) -> bool {
+    matches!(
+        (node, and),
+        (Operator::And(_, _), true) | (Operator::Or(_, _), false)
+    )
+}
+
+// Operands of a chain of And (or Or) nodes, from left to right.
+fn chain(node: &Operator, and: bool) -> Vec<&Operator> {
+    match node {
+        Operator::And(a, b) | Operator::Or(a, b) if same_kind(node, and) => {
+            let mut items = chain(a, and);
+            items.extend(chain(b, and));
+            items
+        }
+        _ => vec![node],
+    }
+}
+
+// `a` makes `b` redundant in a chain when the operands of `a`, seen as a chain
+// of the dual connective, are a subset of those of `b`. That covers
+// idempotence, absorption and clause subsumption.
+fn subsumes(a: &Operator, b: &Operator, and: bool) -> bool {
+    let b = chain(b, !and);
+    chain(a, !and).iter().all(|x| b.contains(x))
+}
+
+fn simplify_chain(node: &Operator, and: bool) -> Operator {
+    let identity = Operator::Constant(and);
+    let absorbing = Operator::Constant(!and);
+    let items = chain(node, and);
+    if items.contains(&&absorbing) {
+        return absorbing;
+    }
+    let items: Vec<&Operator> = items.into_iter().filter(|x| **x != identity).collect();
+    for (i, a) in items.iter().enumerate() {
+        if items[i + 1..].iter().any(|b| is_complement(a, b)) {
+            return absorbing;
+        }
+    }
+    let kept: Vec<&Operator> = items
+        .iter()
+        .enumerate()
+        .filter(|(i, b)| {
+            !items
+                .iter()
+                .enumerate()
+                .any(|(j, a)| j != *i && subsumes(a, b, and) && (j < *i || !subsumes(b, a, and)))
+        })
+        .map(|(_, b)| *b)
+        .collect();
+    if kept.len() == chain(node, and).len() {
+        return node.clone();
+    }
+    kept.into_iter()
+        .cloned()
+        .reduce(|a, b| {
+            if and {
+                Operator::and(a, b)
+            } else {
+                Operator::or(a, b)
+            }
+        })
+        .unwrap_or(identity)
+}
+
+fn simplify_node(node: Operator) -> Operator {
+    use Operator::Constant;
+
+    match node {
+        Operator::Not(a) => match *a {
+            Operator::Not(b) => *b,
+            Constant(b) => Constant(!b),
+            a => Operator::not(a),
+        },
+        Operator::And(_, _) => simplify_chain(&node, true),
+        Operator::Or(_, _) => simplify_chain(&node, false),
+        Operator::Xor(a, b) => match (*a, *b) {
+            (Constant(false), x) | (x, Constant(false)) => x,
+            (Constant(true), x) | (x, Constant(true)) => simplify_node(Operator::not(x)),
+            (a, b) if a == b => Constant(false),
+            (a, b) if is_complement(&a, &b) => Constant(true),
+            (a, b) => Operator::with_two('^', a, b),
+        },
+        Operator::Equals(a, b) => match (*a, *b) {
+            (Constant(true), x) | (x, Constant(true)) => x,
+            (Constant(false), x) | (x, Constant(false)) => simplify_node(Operator::not(x)),
+            (a, b) if a == b => Constant(true),
+            (a, b) if is_complement(&a, &b) => Constant(false),
+            (a, b) => Operator::with_two('=', a, b),
+        },
+        Operator::Implies(a, b) => match (*a, *b) {
+            (Constant(false), _) | (_, Constant(true)) => Constant(true),
+            (Constant(true), x) => x,
+            (x, Constant(false)) => simplify_node(Operator::not(x)),
+            (a, b) if a == b => Constant(true),
+            (a, b) if is_complement(&a, &b) => b,
+            (a, b) => Operator::with_two('>', a, b),
+        },
+        Operator::Operand(_) | Operator::Constant(_) => node,
+    }
+}
+
+fn simplify_once(node: &Operator) -> Operator {
+    let node = match node {
+        Operator::Not(a) => Operator::not(simplify_once(a)),
+        Operator::And(a, b) => Operator::and(simplify_once(a), simplify_once(b)),
+        Operator::Or(a, b) => Operator::or(simplify_once(a), simplify_once(b)),
+        Operator::Xor(a, b) => Operator::with_two('^', simplify_once(a), simplify_once(b)),
+        Operator::Equals(a, b) => Operator::with_two('=', simplify_once(a), simplify_once(b)),
+        Operator::Implies(a, b) => Operator::with_two('>', simplify_once(a), simplify_once(b)),
+        Operator::Operand(_) | Operator::Constant(_) => node.clone(),
+    };
+    simplify_node(node)
+}
+
+impl Operator {
+    // Number of nodes in the tree.
+    pub fn size(&self) -> usize {
+        match self {
+            Operator::And(a, b)
+            | Operator::Or(a, b)
+            | Operator::Xor(a, b)
+            | Operator::Implies(a, b)
+            | Operator::Equals(a, b) => 1 + a.size() + b.size(),
+            Operator::Not(a) => 1 + a.size(),
+            Operator::Operand(_) | Operator::Constant(_) => 1,
+        }
+    }
+
+    // Applies local rewrite rules until nothing changes. Every rule removes
+    // nodes, so the result is never larger than the input.
+    pub fn simplify(&self) -> Operator {
+        let mut current = self.clone();
+        loop {
+            let next = simplify_once(&current);
+            if next == current || next.size() > current.size() {
+                return current;
+            }
+            current = next;
+        }
+    }
+}
+
+pub fn simplify(formula: &str) -> String {
+    match Operator::from_formula(formula) {
+        Some(operator) => operator.simplify().to_string(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conjunctive_normal_form::conjunctive_normal_form;
+    use crate::negation_normal_form::negation_normal_form;
+    use crate::table_diff::equivalent;
+
+    fn tree(formula: &str) -> Operator {
+        Operator::from_formula(formula).unwrap()
+    }
+
+    #[test]
+    fn idempotence_and_double_negation_work() {
+        assert_eq!(simplify("AA&"), "A");
+        assert_eq!(simplify("AB|AB||"), "AB|");
+        assert_eq!(simplify("A!!"), "A");
+        assert_eq!(simplify("AB&!!!"), "AB&!");
+    }
+
+    #[test]
+    fn complementation_works() {
+        assert_eq!(simplify("AA!&"), "0");
+        assert_eq!(simplify("AB&C|AB&!|"), "1");
+        assert_eq!(simplify("AA!^"), "1");
+        assert_eq!(simplify("AA!>"), "A!");
+    }
+
+    #[test]
+    fn constants_are_folded() {
+        assert_eq!(simplify("A1&"), "A");
+        assert_eq!(simplify("A0|B&"), "AB&");
+        assert_eq!(simplify("A1^"), "A!");
+        assert_eq!(simplify("1A>"), "A");
+        assert_eq!(simplify("A0>"), "A!");
+        assert_eq!(simplify("A0="), "A!");
+        assert_eq!(simplify("A0&B|"), "B");
+    }
+
+    #[test]
+    fn absorption_works() {
+        assert_eq!(simplify("AAB|&"), "A");
+        assert_eq!(simplify("AB&A|"), "A");
+        assert_eq!(simplify("AB&BA&|"), "AB&");
+    }
+
+    #[test]
+    fn subsumed_clauses_are_removed() {
+        assert_eq!(simplify("AB|C|AB|&D&"), "AB|D&");
+        assert_eq!(simplify("AB!&CA&B!&|"), "AB!&");
+    }
+
+    #[test]
+    fn normal_forms_get_shorter() {
+        let cnf = tree(&conjunctive_normal_form("AB&A|C!&"));
+
+        assert!(cnf.simplify().size() < cnf.size());
+        assert_eq!(simplify(&negation_normal_form("AA!|!")), "0");
+    }
+
+    #[test]
+    fn simplification_is_equivalent_and_never_grows() {
+        for formula in [
+            "AB=",
+            "AB=C>AC!&|",
+            "AB&A|C^1&",
+            "AB>BA>&AB=!|",
+            "AB|C&A!B!&C!|&",
+            "AB^A!B!^=",
+            "ABC&&A!|B!|C|",
+            "AB0&|A1^>",
+        ] {
+            let operator = tree(formula);
+            let simplified = operator.simplify();
+
+            assert!(simplified.size() <= operator.size(), "{}", formula);
+            assert!(equivalent(&operator, &simplified), "{}", formula);
+        }
+    }
+}
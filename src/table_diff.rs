@@ -0,0 +1,208 @@
+use crate::operator::Operator;
+use crate::truth_table::TruthTable;
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct DiffRow {
+    pub index: u64,
+    pub values: Vec<(char, bool)>,
+    pub left: bool,
+    pub right: bool,
+}
+
+impl DiffRow {
+    pub fn differs(&self) -> bool {
+        self.left != self.right
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct TableDiff {
+    left: TruthTable,
+    right: TruthTable,
+}
+
+impl TableDiff {
+    pub fn new(left: &Operator, right: &Operator) -> TableDiff {
+        let mut operands = left.operands();
+        for c in right.operands() {
+            if !operands.contains(&c) {
+                operands.push(c);
+            }
+        }
+        TableDiff {
+            left: TruthTable::new(left.clone(), &operands),
+            right: TruthTable::new(right.clone(), &operands),
+        }
+    }
+
+    pub fn operands(&self) -> &[char] {
+        self.left.operands()
+    }
+
+    pub fn len(&self) -> u64 {
+        self.left.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = DiffRow> + '_ {
+        self.left
+            .rows()
+            .zip(self.right.rows())
+            .map(|(left, right)| DiffRow {
+                index: left.index,
+                values: left.values,
+                left: left.result,
+                right: right.result,
+            })
+    }
+
+    pub fn differing_rows(&self) -> impl Iterator<Item = DiffRow> + '_ {
+        self.rows().filter(|row| row.differs())
+    }
+
+    pub fn count_differences(&self) -> u64 {
+        self.differing_rows().count() as u64
+    }
+
+    pub fn is_equivalent(&self) -> bool {
+        self.differing_rows().next().is_none()
+    }
+}
+
+pub fn equivalent(left: &Operator, right: &Operator) -> bool {
+    TableDiff::new(left, right).is_equivalent()
+}
+
+// Equal on every row where `dont_care` is false.
+pub fn equivalent_modulo_dont_cares(
+    left: &Operator,
+    right: &Operator,
+    dont_care: &Operator,
+) -> bool {
+    let differences = Operator::and(
+        Operator::with_two('^', left.clone(), right.clone()),
+        Operator::not(dont_care.clone()),
+    );
+    equivalent(&differences, &Operator::Constant(false))
+}
+
+pub fn print_truth_table_diff(left: &str, right: &str) {
+    let (Some(left_operator), Some(right_operator)) =
+        (Operator::from_formula(left), Operator::from_formula(right))
+    else {
+        return;
+    };
+    let diff = TableDiff::new(&left_operator, &right_operator);
+    let mut temp: String = diff
+        .operands()
+        .iter()
+        .map(|c| format!("| {} ", c))
+        .collect();
+    temp.push_str("| L | R |   |");
+    println!("{}", temp);
+    let mut temp: String = diff.operands().iter().map(|_| "|---").collect();
+    temp.push_str("|---|---|---|");
+    println!("{}", temp);
+    let mut differences = 0;
+    for row in diff.rows() {
+        let mut temp: String = row
+            .values
+            .iter()
+            .map(|(_, b)| format!("| {} ", if *b { 1 } else { 0 }))
+            .collect();
+        temp.push_str(&format!(
+            "| {} | {} | {} |",
+            if row.left { 1 } else { 0 },
+            if row.right { 1 } else { 0 },
+            if row.differs() { "*" } else { " " }
+        ));
+        println!("{}", temp);
+        if row.differs() {
+            differences += 1;
+        }
+    }
+    println!(
+        "L = {}, R = {}: {} of {} rows differ",
+        left,
+        right,
+        differences,
+        diff.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(a: &str, b: &str) -> TableDiff {
+        TableDiff::new(
+            &Operator::from_formula(a).unwrap(),
+            &Operator::from_formula(b).unwrap(),
+        )
+    }
+
+    #[test]
+    fn identical_formulas_have_no_differences() {
+        let diff = diff("AB&", "BA&");
+
+        assert_eq!(diff.count_differences(), 0);
+        assert!(diff.is_equivalent());
+    }
+
+    #[test]
+    fn differing_rows_are_reported() {
+        let diff = diff("AB|", "AB^");
+        let rows: Vec<DiffRow> = diff.differing_rows().collect();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].values, vec![('A', true), ('B', true)]);
+        assert!(rows[0].left);
+        assert!(!rows[0].right);
+    }
+
+    #[test]
+    fn operands_are_the_union_of_both_formulas() {
+        let diff = diff("AB&", "BC&");
+
+        assert_eq!(diff.operands(), &['A', 'B', 'C']);
+        assert_eq!(diff.len(), 8);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.count_differences(), 2);
+    }
+
+    #[test]
+    fn formulas_with_different_operands_can_be_equivalent() {
+        assert!(equivalent(
+            &Operator::from_formula("A").unwrap(),
+            &Operator::from_formula("AB&AB!&|").unwrap()
+        ));
+        assert!(!equivalent(
+            &Operator::from_formula("A").unwrap(),
+            &Operator::from_formula("B").unwrap()
+        ));
+    }
+
+    #[test]
+    fn don_t_care_rows_are_ignored() {
+        let tree = |f: &str| Operator::from_formula(f).unwrap();
+
+        assert!(equivalent_modulo_dont_cares(
+            &tree("AB|"),
+            &tree("AB^"),
+            &tree("AB&")
+        ));
+        assert!(!equivalent_modulo_dont_cares(
+            &tree("AB|"),
+            &tree("AB^"),
+            &tree("AB!&")
+        ));
+        assert!(equivalent_modulo_dont_cares(
+            &tree("A"),
+            &tree("C"),
+            &tree("AC^")
+        ));
+    }
+}
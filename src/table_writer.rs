@@ -0,0 +1,363 @@
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use crate::incomplete::{IncompleteFunction, Value};
+use crate::truth_table::TruthTable;
+
+fn bit(b: bool) -> u8 {
+    if b {
+        1
+    } else {
+        0
+    }
+}
+
+pub trait TableWriter {
+    fn header(&mut self, out: &mut dyn Write, operands: &[char]) -> io::Result<()>;
+    fn row(
+        &mut self,
+        out: &mut dyn Write,
+        values: &[(char, bool)],
+        result: Value,
+    ) -> io::Result<()>;
+    fn footer(&mut self, _out: &mut dyn Write) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+pub struct PipeWriter;
+
+impl TableWriter for PipeWriter {
+    fn header(&mut self, out: &mut dyn Write, operands: &[char]) -> io::Result<()> {
+        let mut temp: String = operands.iter().map(|c| format!("| {} ", c)).collect();
+        temp.push_str("| = |");
+        writeln!(out, "{}", temp)?;
+        let mut temp: String = operands.iter().map(|_| "|---").collect();
+        temp.push_str("|---|");
+        writeln!(out, "{}", temp)
+    }
+
+    fn row(
+        &mut self,
+        out: &mut dyn Write,
+        values: &[(char, bool)],
+        result: Value,
+    ) -> io::Result<()> {
+        let mut temp: String = values
+            .iter()
+            .map(|(_, b)| format!("| {} ", bit(*b)))
+            .collect();
+        temp.push_str(&format!("| {} |", result));
+        writeln!(out, "{}", temp)
+    }
+}
+
+pub struct CsvWriter;
+
+impl TableWriter for CsvWriter {
+    fn header(&mut self, out: &mut dyn Write, operands: &[char]) -> io::Result<()> {
+        let mut columns: Vec<String> = operands.iter().map(|c| c.to_string()).collect();
+        columns.push("=".to_string());
+        writeln!(out, "{}", columns.join(","))
+    }
+
+    fn row(
+        &mut self,
+        out: &mut dyn Write,
+        values: &[(char, bool)],
+        result: Value,
+    ) -> io::Result<()> {
+        let mut columns: Vec<String> = values.iter().map(|(_, b)| bit(*b).to_string()).collect();
+        columns.push(result.to_string());
+        writeln!(out, "{}", columns.join(","))
+    }
+}
+
+#[derive(Default)]
+pub struct JsonWriter {
+    rows_written: u64,
+}
+
+impl TableWriter for JsonWriter {
+    fn header(&mut self, out: &mut dyn Write, operands: &[char]) -> io::Result<()> {
+        let operands: Vec<String> = operands.iter().map(|c| format!("\"{}\"", c)).collect();
+        writeln!(out, "{{")?;
+        writeln!(out, "  \"operands\": [{}],", operands.join(", "))?;
+        write!(out, "  \"rows\": [")
+    }
+
+    fn row(
+        &mut self,
+        out: &mut dyn Write,
+        values: &[(char, bool)],
+        result: Value,
+    ) -> io::Result<()> {
+        let values: Vec<String> = values.iter().map(|(_, b)| bit(*b).to_string()).collect();
+        if self.rows_written > 0 {
+            write!(out, ",")?;
+        }
+        self.rows_written += 1;
+        write!(
+            out,
+            "\n    {{\"values\": [{}], \"result\": {}}}",
+            values.join(", "),
+            match result {
+                Value::DontCare => "null".to_string(),
+                _ => result.to_string(),
+            }
+        )
+    }
+
+    fn footer(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        if self.rows_written > 0 {
+            write!(out, "\n  ")?;
+        }
+        writeln!(out, "]")?;
+        writeln!(out, "}}")
+    }
+}
+
+pub struct MarkdownWriter;
+
+impl TableWriter for MarkdownWriter {
+    fn header(&mut self, out: &mut dyn Write, operands: &[char]) -> io::Result<()> {
+        let mut temp: String = operands.iter().map(|c| format!("| {} ", c)).collect();
+        temp.push_str("| **=** |");
+        writeln!(out, "{}", temp)?;
+        let mut temp: String = operands.iter().map(|_| "|:-:").collect();
+        temp.push_str("|:-:|");
+        writeln!(out, "{}", temp)
+    }
+
+    fn row(
+        &mut self,
+        out: &mut dyn Write,
+        values: &[(char, bool)],
+        result: Value,
+    ) -> io::Result<()> {
+        let mut temp: String = values
+            .iter()
+            .map(|(_, b)| format!("| {} ", bit(*b)))
+            .collect();
+        temp.push_str(&format!("| **{}** |", result));
+        writeln!(out, "{}", temp)
+    }
+}
+
+pub struct LatexWriter;
+
+impl TableWriter for LatexWriter {
+    fn header(&mut self, out: &mut dyn Write, operands: &[char]) -> io::Result<()> {
+        writeln!(
+            out,
+            "\\begin{{tabular}}{{{}|c}}",
+            "c".repeat(operands.len())
+        )?;
+        let mut columns: Vec<String> = operands.iter().map(|c| format!("${}$", c)).collect();
+        columns.push("$=$".to_string());
+        writeln!(out, "{} \\\\", columns.join(" & "))?;
+        writeln!(out, "\\hline")
+    }
+
+    fn row(
+        &mut self,
+        out: &mut dyn Write,
+        values: &[(char, bool)],
+        result: Value,
+    ) -> io::Result<()> {
+        let mut columns: Vec<String> = values.iter().map(|(_, b)| bit(*b).to_string()).collect();
+        columns.push(result.to_string());
+        writeln!(out, "{} \\\\", columns.join(" & "))
+    }
+
+    fn footer(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "\\end{{tabular}}")
+    }
+}
+
+pub struct HtmlWriter;
+
+const HTML_RESULT_STYLE: &str = "font-weight: bold; background-color: #fff3b0";
+
+impl TableWriter for HtmlWriter {
+    fn header(&mut self, out: &mut dyn Write, operands: &[char]) -> io::Result<()> {
+        writeln!(out, "<table>")?;
+        writeln!(out, "  <thead>")?;
+        let columns: String = operands.iter().map(|c| format!("<th>{}</th>", c)).collect();
+        writeln!(
+            out,
+            "    <tr>{}<th style=\"{}\">=</th></tr>",
+            columns, HTML_RESULT_STYLE
+        )?;
+        writeln!(out, "  </thead>")?;
+        writeln!(out, "  <tbody>")
+    }
+
+    fn row(
+        &mut self,
+        out: &mut dyn Write,
+        values: &[(char, bool)],
+        result: Value,
+    ) -> io::Result<()> {
+        let columns: String = values
+            .iter()
+            .map(|(_, b)| format!("<td>{}</td>", bit(*b)))
+            .collect();
+        writeln!(
+            out,
+            "    <tr>{}<td style=\"{}\">{}</td></tr>",
+            columns, HTML_RESULT_STYLE, result
+        )
+    }
+
+    fn footer(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "  </tbody>")?;
+        writeln!(out, "</table>")
+    }
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub enum TableFormat {
+    #[default]
+    Pipe,
+    Csv,
+    Json,
+    Markdown,
+    Latex,
+    Html,
+}
+
+impl TableFormat {
+    pub fn writer(self) -> Box<dyn TableWriter> {
+        match self {
+            TableFormat::Pipe => Box::new(PipeWriter),
+            TableFormat::Csv => Box::new(CsvWriter),
+            TableFormat::Json => Box::new(JsonWriter::default()),
+            TableFormat::Markdown => Box::new(MarkdownWriter),
+            TableFormat::Latex => Box::new(LatexWriter),
+            TableFormat::Html => Box::new(HtmlWriter),
+        }
+    }
+}
+
+impl FromStr for TableFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<TableFormat, String> {
+        match s.to_lowercase().as_str() {
+            "pipe" => Ok(TableFormat::Pipe),
+            "csv" => Ok(TableFormat::Csv),
+            "json" => Ok(TableFormat::Json),
+            "markdown" | "md" => Ok(TableFormat::Markdown),
+            "latex" | "tex" => Ok(TableFormat::Latex),
+            "html" => Ok(TableFormat::Html),
+            _ => Err(format!("Unknown table format '{}'", s)),
+        }
+    }
+}
+
+pub fn write_table(
+    table: &TruthTable,
+    writer: &mut dyn TableWriter,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    writer.header(out, table.operands())?;
+    for row in table.rows() {
+        writer.row(out, &row.values, Value::from(row.result))?;
+    }
+    writer.footer(out)
+}
+
+pub fn write_incomplete(
+    function: &IncompleteFunction,
+    writer: &mut dyn TableWriter,
+    out: &mut dyn Write,
+) -> io::Result<()> {
+    writer.header(out, function.operands())?;
+    for index in 0..function.len() {
+        writer.row(out, &function.values(index), function.value(index))?;
+    }
+    writer.footer(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(formula: &str, format: TableFormat) -> String {
+        let table = TruthTable::from_formula(formula).unwrap();
+        let mut out = Vec::new();
+        write_table(&table, format.writer().as_mut(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn pipe_format_matches_printed_table() {
+        assert_eq!(
+            render("A!", TableFormat::Pipe),
+            "| A | = |\n|---|---|\n| 0 | 1 |\n| 1 | 0 |\n"
+        );
+    }
+
+    #[test]
+    fn csv_format_works() {
+        assert_eq!(
+            render("AB&", TableFormat::Csv),
+            "A,B,=\n0,0,0\n0,1,0\n1,0,0\n1,1,1\n"
+        );
+    }
+
+    #[test]
+    fn json_format_works() {
+        assert_eq!(
+            render("A!", TableFormat::Json),
+            "{\n  \"operands\": [\"A\"],\n  \"rows\": [\n    {\"values\": [0], \"result\": 1},\n    {\"values\": [1], \"result\": 0}\n  ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn markdown_format_works() {
+        assert_eq!(
+            render("A!", TableFormat::Markdown),
+            "| A | **=** |\n|:-:|:-:|\n| 0 | **1** |\n| 1 | **0** |\n"
+        );
+    }
+
+    #[test]
+    fn latex_format_works() {
+        assert_eq!(
+            render("A!", TableFormat::Latex),
+            "\\begin{tabular}{c|c}\n$A$ & $=$ \\\\\n\\hline\n0 & 1 \\\\\n1 & 0 \\\\\n\\end{tabular}\n"
+        );
+    }
+
+    #[test]
+    fn html_format_highlights_result() {
+        let html = render("A!", TableFormat::Html);
+
+        assert!(html.starts_with("<table>\n"));
+        assert!(html.ends_with("</table>\n"));
+        assert!(html.contains(&format!(
+            "<tr><td>0</td><td style=\"{}\">1</td></tr>",
+            HTML_RESULT_STYLE
+        )));
+    }
+
+    #[test]
+    fn json_writes_don_t_cares_as_null() {
+        let function = IncompleteFunction::from_table(&['A'], "1-");
+        let mut out = Vec::new();
+        write_incomplete(&function, TableFormat::Json.writer().as_mut(), &mut out).unwrap();
+
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .contains("{\"values\": [1], \"result\": null}"));
+    }
+
+    #[test]
+    fn formats_can_be_parsed() {
+        assert_eq!("CSV".parse(), Ok(TableFormat::Csv));
+        assert_eq!("md".parse(), Ok(TableFormat::Markdown));
+        assert!("xml".parse::<TableFormat>().is_err());
+    }
+}
@@ -1,6 +1,8 @@
+use crate::algebra::evaluate_in;
+use crate::gray_code::gray_code;
 use crate::operator::Operator;
-use itertools::Itertools;
-use std::collections::{HashMap, HashSet};
+use crate::table_writer::{write_table, TableFormat};
+use std::io;
 
 fn operands_in_formula(formula: &str) -> Vec<char> {
     formula
@@ -14,68 +16,275 @@ fn operands_in_formula(formula: &str) -> Vec<char> {
         })
 }
 
-fn print_header(operands: &[char]) {
-    let mut temp: String = operands.iter().map(|c| format!("| {} ", c)).collect();
-    temp.push_str("| = |");
-    println!("{}", temp);
-    let mut temp: String = operands.iter().map(|_| "|---").collect();
-    temp.push_str("|---|");
-    println!("{}", temp);
-}
-
-fn operand_combinations(operands: &[char]) -> Vec<Vec<(char, bool)>> {
-    operands
-        .iter()
-        .cartesian_product(vec![true, false])
-        .map(|(&c, b)| (c, b))
-        .combinations(operands.len())
-        .filter(|c| {
-            let mut seen = HashSet::new();
-            for (c, _) in c {
-                if seen.contains(c) {
-                    return false;
-                }
-                seen.insert(c);
+#[derive(PartialEq, Debug, Clone)]
+pub struct Row {
+    pub index: u64,
+    pub values: Vec<(char, bool)>,
+    pub result: bool,
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub enum RowOrder {
+    #[default]
+    Binary,
+    ReverseBinary,
+    Gray,
+    Weight,
+}
+
+// Smallest index above `index` with the same number of true operands, or the
+// first index of the next weight once a weight is exhausted. After the last
+// weight of a 63 or 64 operand table that index does not fit, u64::MAX is
+// returned instead.
+fn next_by_weight(index: u64, len: u64) -> u64 {
+    if index != 0 {
+        let lowest = index & index.wrapping_neg();
+        if let Some(ripple) = index.checked_add(lowest) {
+            let next = (((ripple ^ index) >> 2) / lowest) | ripple;
+            if next < len {
+                return next;
             }
-            true
-        })
-        .sorted_by(|a, b| {
-            let n_trues = a.iter().fold(0, |a, (_, b)| if *b { a + 1 } else { a });
-            let m_trues = b.iter().fold(0, |a, (_, b)| if *b { a + 1 } else { a });
-            n_trues.cmp(&m_trues)
-        })
-        .collect_vec()
+        }
+    }
+    1u64.checked_shl(index.count_ones() + 1)
+        .map_or(u64::MAX, |bit| bit - 1)
 }
 
-fn print_values(values: &[(char, bool)]) -> String {
-    let mut temp: String = values
-        .iter()
-        .map(|(_, b)| format!("| {} ", if *b { 1 } else { 0 }))
-        .collect();
-    temp.push_str("|");
-    temp
+fn binomial(n: u32, k: u32) -> u64 {
+    if k > n {
+        return 0;
+    }
+    (0..k.min(n - k)).fold(1u128, |acc, i| acc * (n - i) as u128 / (i + 1) as u128) as u64
 }
 
-fn solve(node: &Operator, values: &HashMap<char, bool>) -> bool {
-    match node {
-        Operator::And(a, b) => solve(a, values) && solve(b, values),
-        Operator::Or(a, b) => solve(a, values) || solve(b, values),
-        Operator::Xor(a, b) => solve(a, values) ^ solve(b, values),
-        Operator::Implies(a, b) => !solve(a, values) || solve(b, values),
-        Operator::Equals(a, b) => solve(a, values) == solve(b, values),
-        Operator::Not(a) => !solve(a, values),
-        Operator::Operand(c) => *values.get(c).expect("No value for operand"),
+// Index of the row at `position` in weight order for a table of `bits`
+// operands: the weight is found from the sizes of the lighter weights, then
+// the remaining rank is unranked one bit at a time from the top.
+fn index_by_weight(position: u64, bits: u32) -> u64 {
+    let mut rank = position;
+    let mut weight = 0;
+    while rank >= binomial(bits, weight) {
+        rank -= binomial(bits, weight);
+        weight += 1;
+    }
+    let mut index = 0;
+    for bit in (0..bits).rev() {
+        let below = binomial(bit, weight);
+        if rank >= below {
+            index |= 1 << bit;
+            rank -= below;
+            weight -= 1;
+        }
+    }
+    index
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct TruthTable {
+    operator: Operator,
+    operands: Vec<char>,
+    order: RowOrder,
+}
+
+impl TruthTable {
+    pub fn new(operator: Operator, operands: &[char]) -> TruthTable {
+        if operands.len() >= u64::BITS as usize {
+            panic!("Too many operands for a truth table");
+        }
+        if let Some(c) = operator.operands().iter().find(|c| !operands.contains(c)) {
+            panic!("No column for operand {}", c);
+        }
+        TruthTable {
+            operator,
+            operands: operands.to_vec(),
+            order: RowOrder::default(),
+        }
+    }
+
+    pub fn with_order(mut self, order: RowOrder) -> TruthTable {
+        if order == RowOrder::Gray && self.operands.len() > u32::BITS as usize {
+            panic!("Gray code order supports at most {} operands", u32::BITS);
+        }
+        self.order = order;
+        self
+    }
+
+    pub fn from_formula(formula: &str) -> Option<TruthTable> {
+        let operator = Operator::from_formula(formula)?;
+        let operands = operator.operands();
+        Some(TruthTable::new(operator, &operands))
+    }
+
+    pub fn operator(&self) -> &Operator {
+        &self.operator
+    }
+
+    pub fn operands(&self) -> &[char] {
+        &self.operands
+    }
+
+    pub fn order(&self) -> RowOrder {
+        self.order
+    }
+
+    pub fn len(&self) -> u64 {
+        1 << self.operands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // The first operand is the most significant bit, so row 0 has every
+    // operand false and the last row has every operand true.
+    pub fn row(&self, index: u64) -> Row {
+        if index >= self.len() {
+            panic!("Row {} out of range", index);
+        }
+        let n = self.operands.len();
+        let values: Vec<(char, bool)> = self
+            .operands
+            .iter()
+            .enumerate()
+            .map(|(i, &c)| (c, index >> (n - 1 - i) & 1 == 1))
+            .collect();
+        let result = evaluate_in(&self.operator, &true, &|c| {
+            values
+                .iter()
+                .find(|(operand, _)| *operand == c)
+                .expect("No value for operand")
+                .1
+        });
+        Row {
+            index,
+            values,
+            result,
+        }
+    }
+
+    pub fn minterms(&self) -> Vec<u64> {
+        (0..self.len()).filter(|&i| self.row(i).result).collect()
+    }
+
+    pub fn maxterms(&self) -> Vec<u64> {
+        (0..self.len()).filter(|&i| !self.row(i).result).collect()
+    }
+
+    // Results of 64 consecutive rows at once, bit `j` holds row
+    // `block * 64 + j`. The last operands vary fastest, so they get fixed bit
+    // patterns and the others are constant over the whole block.
+    pub fn block(&self, block: u64) -> u64 {
+        const PATTERNS: [u64; 6] = [
+            0xAAAA_AAAA_AAAA_AAAA,
+            0xCCCC_CCCC_CCCC_CCCC,
+            0xF0F0_F0F0_F0F0_F0F0,
+            0xFF00_FF00_FF00_FF00,
+            0xFFFF_0000_FFFF_0000,
+            0xFFFF_FFFF_0000_0000,
+        ];
+        let n = self.operands.len();
+        let base = block << 6;
+        if base >= self.len() {
+            panic!("Block {} out of range", block);
+        }
+        let result = evaluate_in(&self.operator, &0u64, &|c| {
+            let i = self
+                .operands
+                .iter()
+                .position(|&operand| operand == c)
+                .expect("No value for operand");
+            let bit = n - 1 - i;
+            if bit < PATTERNS.len() {
+                PATTERNS[bit]
+            } else if base >> bit & 1 == 1 {
+                u64::MAX
+            } else {
+                0
+            }
+        });
+        if n < 6 {
+            result & ((1 << self.len()) - 1)
+        } else {
+            result
+        }
+    }
+
+    pub fn blocks(&self) -> u64 {
+        self.len().div_ceil(64)
+    }
+
+    pub fn rows(&self) -> Rows<'_> {
+        self.rows_from(0)
+    }
+
+    pub fn rows_from(&self, position: u64) -> Rows<'_> {
+        let mut rows = Rows {
+            table: self,
+            position: 0,
+            end: self.len(),
+            weight_index: 0,
+        };
+        rows.seek(position);
+        rows
+    }
+}
+
+pub struct Rows<'a> {
+    table: &'a TruthTable,
+    position: u64,
+    end: u64,
+    weight_index: u64,
+}
+
+impl Rows<'_> {
+    fn seek(&mut self, position: u64) {
+        self.position = position.min(self.end);
+        if self.table.order == RowOrder::Weight && self.position < self.end {
+            self.weight_index = index_by_weight(self.position, self.end.trailing_zeros());
+        }
+    }
+
+    fn advance(&mut self) -> Option<u64> {
+        if self.position >= self.end {
+            return None;
+        }
+        let index = match self.table.order {
+            RowOrder::Binary => self.position,
+            RowOrder::ReverseBinary => self.end - 1 - self.position,
+            RowOrder::Gray => gray_code(self.position as u32) as u64,
+            RowOrder::Weight => {
+                let index = self.weight_index;
+                self.weight_index = next_by_weight(index, self.end);
+                index
+            }
+        };
+        self.position += 1;
+        Some(index)
+    }
+}
+
+impl Iterator for Rows<'_> {
+    type Item = Row;
+
+    fn next(&mut self) -> Option<Row> {
+        self.advance().map(|index| self.table.row(index))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Row> {
+        self.seek(self.position.saturating_add(n as u64));
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = usize::try_from(self.end - self.position).ok();
+        (remaining.unwrap_or(usize::MAX), remaining)
     }
 }
 
 pub fn truth_table(operator: &Operator, operands: &[char]) -> Vec<(Vec<(char, bool)>, bool)> {
-    let combinations = operand_combinations(operands);
-    combinations
-        .iter()
-        .map(|comb| {
-            let values = HashMap::from_iter(comb.clone());
-            (comb.clone(), solve(operator, &values))
-        })
+    TruthTable::new(operator.clone(), operands)
+        .rows()
+        .map(|row| (row.values, row.result))
         .collect()
 }
 
@@ -84,30 +293,33 @@ pub fn print_truth_table(formula: &str) {
     if operator.is_none() {
         return;
     }
-    let operator = operator.unwrap();
     let operands = operands_in_formula(formula);
-    print_header(&operands);
-    let table = truth_table(&operator, &operands);
-    for (row, result) in table {
-        let mut values = print_values(&row);
-        values.push_str(format!(" {} |", if result { 1 } else { 0 }).as_str());
-        println!("{}", values);
-    }
+    print_table(&TruthTable::new(operator.unwrap(), &operands));
+}
+
+pub fn print_table(table: &TruthTable) {
+    print_table_as(table, TableFormat::Pipe);
+}
+
+pub fn print_table_as(table: &TruthTable, format: TableFormat) {
+    write_table(table, format.writer().as_mut(), &mut io::stdout().lock())
+        .expect("Could not write truth table");
 }
 
 #[cfg(test)]
 pub mod tests {
     use super::*;
+    use crate::algebra::evaluate;
+    use crate::table_diff::TableDiff;
+    use std::collections::HashMap;
 
     pub fn truth_tables_equal(a: &str, b: &str) {
         let a_operator = Operator::from_formula(a).unwrap();
         let b_operator = Operator::from_formula(b).unwrap();
-        let a_operands = operands_in_formula(a.to_string().as_str());
-        let b_operands = operands_in_formula(b.to_string().as_str());
-        let a_table = truth_table(&a_operator, &a_operands);
-        let b_table = truth_table(&b_operator, &b_operands);
-        for (a_row, b_row) in a_table.iter().zip(b_table.iter()) {
-            assert_eq!(a_row, b_row);
+        let diff = TableDiff::new(&a_operator, &b_operator);
+        let row = diff.differing_rows().next();
+        if let Some(row) = row {
+            panic!("{} and {} differ for {:?}", a, b, row.values);
         }
     }
 
@@ -135,48 +347,210 @@ pub mod tests {
     #[test]
     fn can_solve_for_correct_values_1() {
         let tree = Operator::from_formula("AB&").unwrap();
-        let values = HashMap::from_iter(vec![('A', true), ('B', false)]);
+        let values: HashMap<char, bool> = HashMap::from_iter(vec![('A', true), ('B', false)]);
 
-        assert_eq!(solve(&tree, &values), false);
+        assert!(!evaluate(&tree, &|c| values[&c]).unwrap());
     }
 
     #[test]
     fn can_solve_for_correct_values_2() {
         let tree = Operator::from_formula("AB|").unwrap();
-        let values = HashMap::from_iter(vec![('A', true), ('B', false)]);
+        let values: HashMap<char, bool> = HashMap::from_iter(vec![('A', true), ('B', false)]);
 
-        assert_eq!(solve(&tree, &values), true);
+        assert!(evaluate(&tree, &|c| values[&c]).unwrap());
     }
 
     #[test]
     fn can_solve_for_correct_values_3() {
         let tree = Operator::from_formula("ABCD||=").unwrap();
-        let values = HashMap::from_iter(vec![('A', true), ('B', false), ('C', true), ('D', true)]);
+        let values: HashMap<char, bool> =
+            HashMap::from_iter(vec![('A', true), ('B', false), ('C', true), ('D', true)]);
 
-        assert_eq!(solve(&tree, &values), true);
+        assert!(evaluate(&tree, &|c| values[&c]).unwrap());
     }
 
     #[test]
     fn can_solve_for_correct_values_4() {
         let tree = Operator::from_formula("A").unwrap();
-        let values = HashMap::from_iter(vec![('A', true)]);
+        let values: HashMap<char, bool> = HashMap::from_iter(vec![('A', true)]);
 
-        assert_eq!(solve(&tree, &values), true);
+        assert!(evaluate(&tree, &|c| values[&c]).unwrap());
     }
 
     #[test]
     fn can_solve_for_correct_values_5() {
         let tree = Operator::from_formula("A!!").unwrap();
-        let values = HashMap::from_iter(vec![('A', true)]);
+        let values: HashMap<char, bool> = HashMap::from_iter(vec![('A', true)]);
 
-        assert_eq!(solve(&tree, &values), true);
+        assert!(evaluate(&tree, &|c| values[&c]).unwrap());
     }
 
     #[test]
     fn can_solve_for_correct_values_6() {
         let tree = Operator::from_formula("AB&!").unwrap();
-        let values = HashMap::from_iter(vec![('A', true), ('B', true)]);
+        let values: HashMap<char, bool> = HashMap::from_iter(vec![('A', true), ('B', true)]);
+
+        assert!(!evaluate(&tree, &|c| values[&c]).unwrap());
+    }
+
+    #[test]
+    fn rows_are_counted_in_binary() {
+        let table = TruthTable::from_formula("AB>").unwrap();
+        let rows: Vec<(Vec<bool>, bool)> = table
+            .rows()
+            .map(|row| (row.values.iter().map(|(_, b)| *b).collect(), row.result))
+            .collect();
+
+        assert_eq!(
+            rows,
+            vec![
+                (vec![false, false], true),
+                (vec![false, true], true),
+                (vec![true, false], false),
+                (vec![true, true], true),
+            ]
+        );
+    }
+
+    #[test]
+    fn table_columns_can_include_unused_operands() {
+        let operator = Operator::from_formula("A!").unwrap();
+        let table = TruthTable::new(operator, &['A', 'Z']);
+
+        assert_eq!(table.len(), 4);
+        assert_eq!(table.row(2).values, vec![('A', true), ('Z', false)]);
+        assert!(!table.row(2).result);
+    }
+
+    #[test]
+    #[should_panic]
+    fn table_without_column_for_operand_panics() {
+        let operator = Operator::from_formula("AB&").unwrap();
+        TruthTable::new(operator, &['A']);
+    }
+
+    #[test]
+    fn large_tables_are_streamed() {
+        let table =
+            TruthTable::from_formula("ABCDEFGHIJKLMNOPQRSTUVWXY&&&&&&&&&&&&&&&&&&&&&&&&").unwrap();
+        let mut rows = table.rows();
+
+        assert_eq!(table.len(), 1 << 25);
+        assert_eq!(rows.size_hint(), (1 << 25, Some(1 << 25)));
+        assert!(!rows.next().unwrap().result);
+        let last = rows.nth((1 << 25) - 2).unwrap();
+        assert_eq!(last.index, (1 << 25) - 1);
+        assert!(last.result);
+        assert!(rows.next().is_none());
+    }
+
+    fn indices(table: &TruthTable) -> Vec<u64> {
+        table.rows().map(|row| row.index).collect()
+    }
+
+    #[test]
+    fn rows_can_be_counted_in_reverse() {
+        let table = TruthTable::from_formula("AB&").unwrap();
+
+        assert_eq!(
+            indices(&table.with_order(RowOrder::ReverseBinary)),
+            vec![3, 2, 1, 0]
+        );
+    }
+
+    #[test]
+    fn rows_can_follow_gray_code() {
+        let table = TruthTable::from_formula("ABC&&").unwrap();
+
+        assert_eq!(
+            indices(&table.with_order(RowOrder::Gray)),
+            vec![0, 1, 3, 2, 6, 7, 5, 4]
+        );
+    }
+
+    #[test]
+    fn rows_can_be_sorted_by_weight() {
+        let table = TruthTable::from_formula("ABC&&").unwrap();
+
+        assert_eq!(
+            indices(&table.with_order(RowOrder::Weight)),
+            vec![0, 1, 2, 4, 3, 5, 6, 7]
+        );
+    }
+
+    #[test]
+    fn skipping_rows_by_weight_matches_iterating() {
+        let table = TruthTable::from_formula("ABCD&&&")
+            .unwrap()
+            .with_order(RowOrder::Weight);
+        let all = indices(&table);
+
+        for (n, index) in all.iter().enumerate() {
+            assert_eq!(table.rows().nth(n).unwrap().index, *index);
+        }
+        assert!(table.rows().nth(all.len()).is_none());
+        for (n, index) in all.iter().enumerate() {
+            assert_eq!(table.rows_from(n as u64).next().unwrap().index, *index);
+        }
+    }
+
+    #[test]
+    fn weight_order_is_unranked_directly() {
+        for bits in 0..8 {
+            let mut index = 0;
+            for position in 0..1u64 << bits {
+                assert_eq!(index_by_weight(position, bits), index);
+                index = next_by_weight(index, 1 << bits);
+            }
+        }
+        assert_eq!(index_by_weight(63, 63), 1 << 62);
+        assert_eq!(index_by_weight(64, 63), 0b11);
+        assert_eq!(index_by_weight((1 << 63) - 2, 63), (u64::MAX >> 1) ^ 1);
+        assert_eq!(index_by_weight((1 << 63) - 1, 63), u64::MAX >> 1);
+    }
+
+    #[test]
+    fn weight_order_survives_the_last_weight() {
+        assert_eq!(next_by_weight(u64::MAX >> 1, 1 << 63), u64::MAX);
+        assert_eq!(next_by_weight(u64::MAX, u64::MAX), u64::MAX);
+        assert_eq!(next_by_weight(0b111, 8), 0b1111);
+    }
+
+    #[test]
+    fn variable_order_decides_the_columns() {
+        let operator = Operator::from_formula("AB!&").unwrap();
+        let table = TruthTable::new(operator, &['B', 'A']);
+
+        assert_eq!(table.row(1).values, vec![('B', false), ('A', true)]);
+        assert!(table.row(1).result);
+    }
+
+    #[test]
+    fn blocks_match_rows() {
+        for formula in ["AB^", "ABCDEFG&|^&|>", "ABCDEFGHI&|^&|>!=^"] {
+            let table = TruthTable::from_formula(formula).unwrap();
+            for row in table.rows() {
+                let block = table.block(row.index / 64);
+                assert_eq!(block >> (row.index % 64) & 1 == 1, row.result);
+            }
+        }
+    }
+
+    #[test]
+    fn small_blocks_only_hold_existing_rows() {
+        let table = TruthTable::from_formula("A!").unwrap();
+
+        assert_eq!(table.blocks(), 1);
+        assert_eq!(table.block(0), 0b01);
+    }
+
+    #[test]
+    fn rows_can_start_at_a_position() {
+        let table = TruthTable::from_formula("ABC&&")
+            .unwrap()
+            .with_order(RowOrder::Weight);
+        let rest: Vec<u64> = table.rows_from(3).map(|row| row.index).collect();
 
-        assert_eq!(solve(&tree, &values), false);
+        assert_eq!(rest, vec![4, 3, 5, 6, 7]);
     }
 }